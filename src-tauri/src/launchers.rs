@@ -0,0 +1,431 @@
+//! 启动器模块
+//!
+//! 维护“打开方式”列表（IDE、编辑器、终端等），持久化到 `~/.ytools/launchers.json`。
+//! 每个启动器由可执行文件、参数模板（支持 `{path}`、`{name}` 占位符）、工作目录和环境变量组成，
+//! 搜索结果可以用任一启动器打开；列表顺序对应搜索窗口中的 `Ctrl+1/2/3…`。
+//! 本机已安装的编辑器与终端可通过 [`detect`] 自动探测。
+
+mod detect;
+
+pub use detect::detect_launchers;
+pub(crate) use detect::split_exec;
+
+use crate::search::{resolve_hit_path, FrecencyStore, SearchDirectoryInput};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+
+/// 启动器列表文件名（位于 .ytools 目录）
+const LAUNCHERS_FILE: &str = "launchers.json";
+
+/// 工作区绝对路径占位符
+const PATH_PLACEHOLDER: &str = "{path}";
+
+/// 工作区文件夹名占位符
+const NAME_PLACEHOLDER: &str = "{name}";
+
+/// 启动器定义
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Launcher {
+    /// 唯一标识，添加时为空则由名称生成
+    #[serde(default)]
+    pub id: String,
+    /// 显示名称（如 "VS Code"）
+    pub name: String,
+    /// 可执行文件：绝对路径，或在 PATH 中查找的命令名
+    pub executable: String,
+    /// 参数模板，每一项单独传给程序（不经过 shell）
+    #[serde(default = "default_args")]
+    pub args: Vec<String>,
+    /// 工作目录，同样支持占位符，为空时使用工作区目录
+    #[serde(default)]
+    pub working_dir: Option<String>,
+    /// 额外的环境变量
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+}
+
+fn default_args() -> Vec<String> {
+    vec![PATH_PLACEHOLDER.to_string()]
+}
+
+/// 一次扫描展开模板中的占位符，替换进来的路径或名称中即使含有占位符也不会再次展开
+pub(crate) fn expand_placeholders(template: &str, path: &str, name: &str) -> String {
+    let mut expanded = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        expanded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        if let Some(after) = rest.strip_prefix(PATH_PLACEHOLDER) {
+            expanded.push_str(path);
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix(NAME_PLACEHOLDER) {
+            expanded.push_str(name);
+            rest = after;
+        } else {
+            expanded.push('{');
+            rest = &rest[1..];
+        }
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+impl Launcher {
+    /// 为指定工作区构建启动命令，可执行文件不存在时返回错误
+    pub fn command(&self, workspace: &Path) -> Result<Command, String> {
+        let executable = self.executable_path()?;
+        let path = workspace.to_string_lossy();
+        let name = workspace
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_else(|| path.clone());
+        let expand = |template: &str| expand_placeholders(template, &path, &name);
+
+        let mut command = Command::new(executable);
+        command
+            .args(self.args.iter().map(|arg| expand(arg)))
+            .envs(&self.env);
+        match &self.working_dir {
+            Some(dir) if !dir.trim().is_empty() => command.current_dir(expand(dir)),
+            _ => command.current_dir(workspace),
+        };
+        Ok(command)
+    }
+
+    /// 检查定义是否完整、可执行文件是否存在
+    fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("启动器名称不能为空".to_string());
+        }
+        if self.executable.trim().is_empty() {
+            return Err("启动器的可执行文件不能为空".to_string());
+        }
+        self.executable_path().map(|_| ())
+    }
+
+    fn executable_path(&self) -> Result<PathBuf, String> {
+        resolve_executable(&self.executable).ok_or_else(|| {
+            format!(
+                "找不到启动器“{}”的可执行文件: {}",
+                self.name, self.executable
+            )
+        })
+    }
+}
+
+/// 查找可执行文件：带路径分隔符的按路径检查，否则依次在 PATH 中查找
+pub fn resolve_executable(executable: &str) -> Option<PathBuf> {
+    let candidate = Path::new(executable);
+    if candidate.is_absolute() || candidate.components().count() > 1 {
+        return candidate.is_file().then(|| candidate.to_path_buf());
+    }
+
+    let paths = std::env::var_os("PATH")?;
+    std::env::split_paths(&paths).find_map(|dir| {
+        executable_names(executable)
+            .into_iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+    })
+}
+
+/// 可执行文件可能的文件名（Windows 下按 PATHEXT 补全扩展名）
+fn executable_names(executable: &str) -> Vec<String> {
+    let mut names = vec![executable.to_string()];
+    if cfg!(target_os = "windows") && Path::new(executable).extension().is_none() {
+        let extensions =
+            std::env::var("PATHEXT").unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_string());
+        names.extend(
+            extensions
+                .split(';')
+                .filter(|ext| !ext.is_empty())
+                .map(|ext| format!("{}{}", executable, ext.to_lowercase())),
+        );
+    }
+    names
+}
+
+/// 由名称生成标识：小写字母数字，其余字符替换为 `-`
+fn slugify(name: &str) -> String {
+    let slug: String = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    slug.split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// 启动器存储（作为 Tauri 托管状态共享）
+pub struct LauncherStore {
+    /// 持久化文件路径，为 None 时仅保存在内存中
+    file: Option<PathBuf>,
+    launchers: Mutex<Vec<Launcher>>,
+}
+
+impl LauncherStore {
+    /// 从 `~/.ytools/launchers.json` 加载，无法定位目录时退化为内存存储
+    pub fn load_default() -> Self {
+        match crate::notes::get_ytools_dir() {
+            Ok(dir) => Self::load(dir.join(LAUNCHERS_FILE)),
+            Err(_) => Self::in_memory(),
+        }
+    }
+
+    /// 从指定文件加载，文件不存在或内容损坏时从空列表开始
+    pub fn load(file: PathBuf) -> Self {
+        let launchers = fs::read_to_string(&file)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self {
+            file: Some(file),
+            launchers: Mutex::new(launchers),
+        }
+    }
+
+    /// 不落盘的内存存储
+    pub fn in_memory() -> Self {
+        Self {
+            file: None,
+            launchers: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn list(&self) -> Vec<Launcher> {
+        self.launchers
+            .lock()
+            .map(|launchers| launchers.clone())
+            .unwrap_or_default()
+    }
+
+    /// 添加启动器并保存；标识已存在时替换原定义（保持原有顺序），返回保存后的定义
+    pub fn add(&self, mut launcher: Launcher) -> Result<Launcher, String> {
+        launcher.validate()?;
+        if launcher.id.trim().is_empty() {
+            launcher.id = slugify(&launcher.name);
+        }
+        if launcher.id.is_empty() {
+            return Err("无法由启动器名称生成标识".to_string());
+        }
+
+        let mut launchers = self.lock()?;
+        match launchers
+            .iter_mut()
+            .find(|existing| existing.id == launcher.id)
+        {
+            Some(existing) => *existing = launcher.clone(),
+            None => launchers.push(launcher.clone()),
+        }
+        self.save(&launchers)?;
+        Ok(launcher)
+    }
+
+    /// 删除启动器并保存
+    pub fn remove(&self, id: &str) -> Result<(), String> {
+        let mut launchers = self.lock()?;
+        let before = launchers.len();
+        launchers.retain(|launcher| launcher.id != id);
+        if launchers.len() == before {
+            return Err(format!("启动器不存在: {}", id));
+        }
+        self.save(&launchers)
+    }
+
+    pub fn get(&self, id: &str) -> Result<Launcher, String> {
+        self.lock()?
+            .iter()
+            .find(|launcher| launcher.id == id)
+            .cloned()
+            .ok_or_else(|| format!("启动器不存在: {}", id))
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, Vec<Launcher>>, String> {
+        self.launchers
+            .lock()
+            .map_err(|_| "启动器列表已损坏".to_string())
+    }
+
+    fn save(&self, launchers: &[Launcher]) -> Result<(), String> {
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+        let content = serde_json::to_string_pretty(launchers)
+            .map_err(|e| format!("序列化启动器失败: {}", e))?;
+        fs::write(file, content).map_err(|e| format!("保存启动器失败: {}", e))
+    }
+}
+
+/// 获取所有启动器
+#[tauri::command]
+pub fn list_launchers(store: tauri::State<'_, LauncherStore>) -> Vec<Launcher> {
+    store.list()
+}
+
+/// 添加（或按标识替换）启动器
+#[tauri::command]
+pub fn add_launcher(
+    store: tauri::State<'_, LauncherStore>,
+    launcher: Launcher,
+) -> Result<Launcher, String> {
+    store.add(launcher)
+}
+
+/// 删除启动器
+#[tauri::command]
+pub fn remove_launcher(store: tauri::State<'_, LauncherStore>, id: String) -> Result<(), String> {
+    store.remove(&id)
+}
+
+/// 用指定启动器打开搜索结果，并记录打开历史
+#[tauri::command]
+pub fn invoke_launcher(
+    store: tauri::State<'_, LauncherStore>,
+    frecency: tauri::State<'_, FrecencyStore>,
+    id: String,
+    path: String,
+    directories: Vec<SearchDirectoryInput>,
+) -> Result<(), String> {
+    let launcher = store.get(&id)?;
+    let workspace = resolve_hit_path(&path, directories)?;
+    launcher
+        .command(Path::new(&workspace))?
+        .spawn()
+        .map_err(|e| format!("启动“{}”失败: {}", launcher.name, e))?;
+
+    frecency.record_open_best_effort(&workspace);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn launcher(name: &str, executable: &str) -> Launcher {
+        Launcher {
+            id: String::new(),
+            name: name.to_string(),
+            executable: executable.to_string(),
+            args: default_args(),
+            working_dir: None,
+            env: BTreeMap::new(),
+        }
+    }
+
+    /// 测试进程自身，保证可执行文件存在
+    fn current_exe() -> String {
+        std::env::current_exe()
+            .unwrap()
+            .to_string_lossy()
+            .to_string()
+    }
+
+    #[test]
+    fn test_store_add_replace_remove_and_persist() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join(LAUNCHERS_FILE);
+        let store = LauncherStore::load(file.clone());
+
+        let added = store.add(launcher("VS Code", &current_exe())).unwrap();
+        assert_eq!(added.id, "vs-code");
+        store.add(launcher("Terminal", &current_exe())).unwrap();
+
+        // 同一标识替换原定义，顺序不变
+        let mut replaced = launcher("VS Code", &current_exe());
+        replaced.args = vec!["--new-window".to_string(), "{path}".to_string()];
+        store.add(replaced).unwrap();
+        let reloaded = LauncherStore::load(file);
+        let launchers = reloaded.list();
+        assert_eq!(launchers.len(), 2);
+        assert_eq!(launchers[0].args[0], "--new-window");
+        assert_eq!(launchers[1].id, "terminal");
+
+        reloaded.remove("terminal").unwrap();
+        assert_eq!(reloaded.list().len(), 1);
+        assert!(reloaded.remove("terminal").is_err());
+    }
+
+    #[test]
+    fn test_missing_executable_is_rejected() {
+        let store = LauncherStore::in_memory();
+        let error = store
+            .add(launcher("Ghost IDE", "ytools-no-such-editor"))
+            .unwrap_err();
+        assert!(error.contains("Ghost IDE"));
+        assert!(error.contains("ytools-no-such-editor"));
+
+        let missing = launcher("Gone", "/nonexistent/bin/editor");
+        assert!(missing.command(Path::new("/tmp")).is_err());
+        assert!(store.add(launcher("", &current_exe())).is_err());
+    }
+
+    #[test]
+    fn test_command_expands_placeholders() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = temp_dir.path().join("my-app");
+        fs::create_dir_all(&workspace).unwrap();
+
+        let mut launcher = launcher("Editor", &current_exe());
+        launcher.args = vec![
+            "--title={name}".to_string(),
+            "{path}".to_string(),
+            "--literal".to_string(),
+        ];
+        launcher.working_dir = Some("{path}".to_string());
+        launcher
+            .env
+            .insert("PROJECT".to_string(), "{name}".to_string());
+
+        let command = launcher.command(&workspace).unwrap();
+        let args: Vec<String> = command
+            .get_args()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect();
+        assert_eq!(
+            args,
+            vec![
+                "--title=my-app".to_string(),
+                workspace.to_string_lossy().to_string(),
+                "--literal".to_string(),
+            ]
+        );
+        assert_eq!(command.get_current_dir(), Some(workspace.as_path()));
+        // 环境变量原样传递，不展开占位符
+        let envs: Vec<_> = command.get_envs().collect();
+        assert_eq!(envs.len(), 1);
+    }
+
+    #[test]
+    fn test_expand_placeholders_single_pass() {
+        // 路径中的 {name} 不会被再次替换
+        assert_eq!(
+            expand_placeholders("{path} --name={name}", "/work/{name}", "{name}"),
+            "/work/{name} --name={name}"
+        );
+        assert_eq!(
+            expand_placeholders("{{path}} {other} {", "/p", "n"),
+            "{/p} {other} {"
+        );
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("IntelliJ IDEA"), "intellij-idea");
+        assert_eq!(slugify("  Sublime  Text 4 "), "sublime-text-4");
+        assert_eq!(slugify("终端"), "终端");
+    }
+}
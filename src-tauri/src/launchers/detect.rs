@@ -0,0 +1,744 @@
+//! 启动器自动探测
+//!
+//! 在 PATH 和各系统的标准安装位置中查找常见的编辑器/IDE 与终端，生成可直接保存的启动器定义；
+//! Vim/Neovim 等终端编辑器借助探测到的终端打开。Linux 下还会读取 XDG 应用目录中的 `.desktop` 文件，
+//! 补充表中没有列出的编辑器与终端。
+
+use super::{slugify, Launcher, PATH_PLACEHOLDER};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 探测时区分的操作系统
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Os {
+    Windows,
+    Mac,
+    Linux,
+}
+
+impl Os {
+    fn current() -> Self {
+        if cfg!(target_os = "windows") {
+            Self::Windows
+        } else if cfg!(target_os = "macos") {
+            Self::Mac
+        } else {
+            Self::Linux
+        }
+    }
+}
+
+/// 已知的应用及其在各系统中的安装位置（`~` 与 `%VAR%` 会被展开）
+struct KnownApp {
+    id: &'static str,
+    name: &'static str,
+    /// PATH 中的命令名
+    commands: &'static [&'static str],
+    windows: &'static [&'static str],
+    macos: &'static [&'static str],
+    linux: &'static [&'static str],
+    /// macOS 下的应用名，找不到命令时通过 `open -a` 启动
+    mac_app: Option<&'static str>,
+}
+
+impl KnownApp {
+    fn locations(&self, os: Os) -> &'static [&'static str] {
+        match os {
+            Os::Windows => self.windows,
+            Os::Mac => self.macos,
+            Os::Linux => self.linux,
+        }
+    }
+}
+
+const EDITORS: &[KnownApp] = &[
+    KnownApp {
+        id: "vscode",
+        name: "VS Code",
+        commands: &["code"],
+        windows: &[
+            r"%LOCALAPPDATA%\Programs\Microsoft VS Code\Code.exe",
+            r"%ProgramFiles%\Microsoft VS Code\Code.exe",
+        ],
+        macos: &["/Applications/Visual Studio Code.app/Contents/Resources/app/bin/code"],
+        linux: &["/usr/share/code/bin/code", "/snap/bin/code"],
+        mac_app: Some("Visual Studio Code"),
+    },
+    KnownApp {
+        id: "cursor",
+        name: "Cursor",
+        commands: &["cursor"],
+        windows: &[r"%LOCALAPPDATA%\Programs\cursor\Cursor.exe"],
+        macos: &["/Applications/Cursor.app/Contents/Resources/app/bin/cursor"],
+        linux: &["/opt/Cursor/cursor", "/opt/cursor/cursor"],
+        mac_app: Some("Cursor"),
+    },
+    KnownApp {
+        id: "zed",
+        name: "Zed",
+        commands: &["zed", "zeditor"],
+        windows: &[],
+        macos: &["/Applications/Zed.app/Contents/MacOS/cli"],
+        linux: &["~/.local/bin/zed"],
+        mac_app: Some("Zed"),
+    },
+    KnownApp {
+        id: "sublime-text",
+        name: "Sublime Text",
+        commands: &["subl"],
+        windows: &[
+            r"%ProgramFiles%\Sublime Text\subl.exe",
+            r"%ProgramFiles%\Sublime Text 3\subl.exe",
+        ],
+        macos: &["/Applications/Sublime Text.app/Contents/SharedSupport/bin/subl"],
+        linux: &["/opt/sublime_text/sublime_text"],
+        mac_app: Some("Sublime Text"),
+    },
+];
+
+/// JetBrains IDE：标识、名称、Toolbox 生成的脚本名、macOS 应用名
+const JETBRAINS_IDES: &[(&str, &str, &str, &str)] = &[
+    ("idea", "IntelliJ IDEA", "idea", "IntelliJ IDEA"),
+    ("pycharm", "PyCharm", "pycharm", "PyCharm"),
+    ("webstorm", "WebStorm", "webstorm", "WebStorm"),
+    ("goland", "GoLand", "goland", "GoLand"),
+    ("clion", "CLion", "clion", "CLion"),
+    ("rider", "Rider", "rider", "Rider"),
+    ("rustrover", "RustRover", "rustrover", "RustRover"),
+    ("phpstorm", "PhpStorm", "phpstorm", "PhpStorm"),
+];
+
+/// 终端及其参数：`open_args` 在工作区打开终端，`exec_args` 在工作区中运行命令（命令追加在后面）
+struct KnownTerminal {
+    app: KnownApp,
+    open_args: &'static [&'static str],
+    /// 为空表示不支持运行命令
+    exec_args: &'static [&'static str],
+}
+
+const TERMINALS: &[KnownTerminal] = &[
+    KnownTerminal {
+        app: KnownApp {
+            id: "windows-terminal",
+            name: "Windows Terminal",
+            commands: &["wt"],
+            windows: &[r"%LOCALAPPDATA%\Microsoft\WindowsApps\wt.exe"],
+            macos: &[],
+            linux: &[],
+            mac_app: None,
+        },
+        open_args: &["-d", "{path}"],
+        exec_args: &["-d", "{path}"],
+    },
+    KnownTerminal {
+        app: KnownApp {
+            id: "gnome-terminal",
+            name: "GNOME Terminal",
+            commands: &["gnome-terminal"],
+            windows: &[],
+            macos: &[],
+            linux: &[],
+            mac_app: None,
+        },
+        open_args: &["--working-directory={path}"],
+        exec_args: &["--working-directory={path}", "--"],
+    },
+    KnownTerminal {
+        app: KnownApp {
+            id: "konsole",
+            name: "Konsole",
+            commands: &["konsole"],
+            windows: &[],
+            macos: &[],
+            linux: &[],
+            mac_app: None,
+        },
+        open_args: &["--workdir", "{path}"],
+        exec_args: &["--workdir", "{path}", "-e"],
+    },
+    KnownTerminal {
+        app: KnownApp {
+            id: "kitty",
+            name: "kitty",
+            commands: &["kitty"],
+            windows: &[],
+            macos: &["/Applications/kitty.app/Contents/MacOS/kitty"],
+            linux: &["~/.local/kitty.app/bin/kitty"],
+            mac_app: None,
+        },
+        open_args: &["--directory", "{path}"],
+        exec_args: &["--directory", "{path}"],
+    },
+    KnownTerminal {
+        app: KnownApp {
+            id: "alacritty",
+            name: "Alacritty",
+            commands: &["alacritty"],
+            windows: &[r"%ProgramFiles%\Alacritty\alacritty.exe"],
+            macos: &["/Applications/Alacritty.app/Contents/MacOS/alacritty"],
+            linux: &[],
+            mac_app: None,
+        },
+        open_args: &["--working-directory", "{path}"],
+        exec_args: &["--working-directory", "{path}", "-e"],
+    },
+    KnownTerminal {
+        app: KnownApp {
+            id: "wezterm",
+            name: "WezTerm",
+            commands: &["wezterm"],
+            windows: &[r"%ProgramFiles%\WezTerm\wezterm.exe"],
+            macos: &["/Applications/WezTerm.app/Contents/MacOS/wezterm"],
+            linux: &[],
+            mac_app: None,
+        },
+        open_args: &["start", "--cwd", "{path}"],
+        exec_args: &["start", "--cwd", "{path}", "--"],
+    },
+    KnownTerminal {
+        app: KnownApp {
+            id: "xfce4-terminal",
+            name: "Xfce Terminal",
+            commands: &["xfce4-terminal"],
+            windows: &[],
+            macos: &[],
+            linux: &[],
+            mac_app: None,
+        },
+        open_args: &["--working-directory={path}"],
+        exec_args: &["--working-directory={path}", "-x"],
+    },
+    KnownTerminal {
+        app: KnownApp {
+            id: "xterm",
+            name: "XTerm",
+            commands: &["xterm"],
+            windows: &[],
+            macos: &[],
+            linux: &[],
+            mac_app: None,
+        },
+        open_args: &[],
+        exec_args: &["-e"],
+    },
+    KnownTerminal {
+        app: KnownApp {
+            id: "iterm",
+            name: "iTerm",
+            commands: &[],
+            windows: &[],
+            macos: &[],
+            linux: &[],
+            mac_app: Some("iTerm"),
+        },
+        open_args: &["{path}"],
+        exec_args: &[],
+    },
+    KnownTerminal {
+        app: KnownApp {
+            id: "terminal",
+            name: "Terminal",
+            commands: &[],
+            windows: &[],
+            macos: &[],
+            linux: &[],
+            mac_app: Some("Terminal"),
+        },
+        open_args: &["{path}"],
+        exec_args: &[],
+    },
+];
+
+/// 在终端中运行的编辑器：标识、名称、命令
+const TERMINAL_EDITORS: &[(&str, &str, &str)] =
+    &[("neovim", "Neovim", "nvim"), ("vim", "Vim", "vim")];
+
+/// 这些分类的 `.desktop` 应用会被识别为编辑器或终端
+const EDITOR_CATEGORIES: &[&str] = &["TextEditor", "IDE"];
+const TERMINAL_CATEGORY: &str = "TerminalEmulator";
+
+/// 探测所需的环境，便于测试时替换
+struct DetectEnv {
+    os: Os,
+    path_dirs: Vec<PathBuf>,
+    home: Option<PathBuf>,
+    /// XDG 数据目录（其下的 `applications` 目录存放 `.desktop` 文件）
+    data_dirs: Vec<PathBuf>,
+}
+
+impl DetectEnv {
+    fn current() -> Self {
+        let os = Os::current();
+        let path_dirs = std::env::var_os("PATH")
+            .map(|paths| std::env::split_paths(&paths).collect())
+            .unwrap_or_default();
+        let home = dirs::home_dir();
+        let data_dirs = if os == Os::Linux {
+            xdg_data_dirs(home.as_deref())
+        } else {
+            Vec::new()
+        };
+        Self {
+            os,
+            path_dirs,
+            home,
+            data_dirs,
+        }
+    }
+
+    /// 在 PATH 中查找命令
+    fn find_command(&self, command: &str) -> Option<PathBuf> {
+        self.path_dirs.iter().find_map(|dir| {
+            super::executable_names(command)
+                .into_iter()
+                .map(|name| dir.join(name))
+                .find(|path| path.is_file())
+        })
+    }
+
+    /// 展开安装位置中的 `~` 与 `%VAR%`，存在时返回路径
+    fn find_location(&self, location: &str) -> Option<PathBuf> {
+        let mut expanded = match location.strip_prefix("~/") {
+            Some(rest) => self.home.as_ref()?.join(rest).to_string_lossy().to_string(),
+            None => location.to_string(),
+        };
+        while let Some(start) = expanded.find('%') {
+            let end = start + 1 + expanded[start + 1..].find('%')?;
+            let value = std::env::var(&expanded[start + 1..end]).ok()?;
+            expanded.replace_range(start..=end, &value);
+        }
+        let path = PathBuf::from(expanded);
+        path.is_file().then_some(path)
+    }
+
+    /// macOS 下应用包是否已安装
+    fn has_mac_app(&self, app: &str) -> bool {
+        let bundle = format!("{}.app", app);
+        let mut dirs = vec![
+            PathBuf::from("/Applications"),
+            PathBuf::from("/System/Applications/Utilities"),
+        ];
+        dirs.extend(self.home.as_ref().map(|home| home.join("Applications")));
+        dirs.iter().any(|dir| dir.join(&bundle).is_dir())
+    }
+
+    /// 查找应用：先查 PATH，再查标准安装位置，最后在 macOS 上通过 `open -a` 启动应用包
+    fn find_app(&self, app: &KnownApp, args: &[&str]) -> Option<Launcher> {
+        let executable = app
+            .commands
+            .iter()
+            .find_map(|command| self.find_command(command))
+            .or_else(|| {
+                app.locations(self.os)
+                    .iter()
+                    .find_map(|location| self.find_location(location))
+            });
+        if let Some(executable) = executable {
+            return Some(launcher(app.id, app.name, &executable, args));
+        }
+
+        let mac_app = app.mac_app.filter(|_| self.os == Os::Mac)?;
+        if !self.has_mac_app(mac_app) {
+            return None;
+        }
+        let open = self.find_command("open")?;
+        let mut open_args = vec!["-a", mac_app];
+        open_args.extend(args.iter().copied().filter(|arg| arg.contains('{')));
+        Some(launcher(app.id, app.name, &open, &open_args))
+    }
+}
+
+/// XDG 数据目录：`$XDG_DATA_HOME`（默认 `~/.local/share`）、`$XDG_DATA_DIRS` 与 Flatpak 导出目录
+fn xdg_data_dirs(home: Option<&Path>) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    match std::env::var_os("XDG_DATA_HOME") {
+        Some(data_home) if !data_home.is_empty() => dirs.push(PathBuf::from(data_home)),
+        _ => dirs.extend(home.map(|home| home.join(".local/share"))),
+    }
+    match std::env::var_os("XDG_DATA_DIRS") {
+        Some(data_dirs) if !data_dirs.is_empty() => dirs.extend(std::env::split_paths(&data_dirs)),
+        _ => dirs.extend([
+            PathBuf::from("/usr/local/share"),
+            PathBuf::from("/usr/share"),
+        ]),
+    }
+    dirs.extend(home.map(|home| home.join(".local/share/flatpak/exports/share")));
+    dirs.push(PathBuf::from("/var/lib/flatpak/exports/share"));
+    dirs
+}
+
+fn launcher(id: &str, name: &str, executable: &Path, args: &[&str]) -> Launcher {
+    Launcher {
+        id: id.to_string(),
+        name: name.to_string(),
+        executable: executable.to_string_lossy().to_string(),
+        args: args.iter().map(|arg| arg.to_string()).collect(),
+        working_dir: None,
+        env: BTreeMap::new(),
+    }
+}
+
+/// 探测本机已安装的编辑器与终端，返回可直接保存的启动器定义（不会自动保存）
+#[tauri::command]
+pub fn detect_launchers() -> Vec<Launcher> {
+    detect(&DetectEnv::current())
+}
+
+fn detect(env: &DetectEnv) -> Vec<Launcher> {
+    let mut launchers: Vec<Launcher> = EDITORS
+        .iter()
+        .filter_map(|app| env.find_app(app, &[PATH_PLACEHOLDER]))
+        .collect();
+
+    for (id, name, script, mac_app) in JETBRAINS_IDES {
+        let locations = [
+            format!(r"%LOCALAPPDATA%\JetBrains\Toolbox\scripts\{}.cmd", script),
+            format!(
+                "~/Library/Application Support/JetBrains/Toolbox/scripts/{}",
+                script
+            ),
+            format!("~/.local/share/JetBrains/Toolbox/scripts/{}", script),
+        ];
+        let executable = env.find_command(script).or_else(|| {
+            locations
+                .iter()
+                .find_map(|location| env.find_location(location))
+        });
+        match executable {
+            Some(executable) => {
+                launchers.push(launcher(id, name, &executable, &[PATH_PLACEHOLDER]));
+            }
+            None if env.os == Os::Mac && env.has_mac_app(mac_app) => {
+                if let Some(open) = env.find_command("open") {
+                    launchers.push(launcher(
+                        id,
+                        name,
+                        &open,
+                        &["-a", mac_app, PATH_PLACEHOLDER],
+                    ));
+                }
+            }
+            None => {}
+        }
+    }
+
+    // 终端编辑器使用第一个支持运行命令的终端
+    let mut terminal_runner: Option<(Launcher, &[&str])> = None;
+    for terminal in TERMINALS {
+        let Some(found) = env.find_app(&terminal.app, terminal.open_args) else {
+            continue;
+        };
+        let runs_commands =
+            !terminal.exec_args.is_empty() && !found.args.starts_with(&["-a".to_string()]);
+        if terminal_runner.is_none() && runs_commands {
+            terminal_runner = Some((found.clone(), terminal.exec_args));
+        }
+        launchers.push(found);
+    }
+    if let Some((terminal, exec_args)) = &terminal_runner {
+        for (id, name, command) in TERMINAL_EDITORS {
+            let Some(editor) = env.find_command(command) else {
+                continue;
+            };
+            let mut args: Vec<String> = exec_args.iter().map(|arg| arg.to_string()).collect();
+            args.push(editor.to_string_lossy().to_string());
+            args.push(PATH_PLACEHOLDER.to_string());
+            launchers.push(Launcher {
+                id: format!("{}-{}", id, terminal.id),
+                name: format!("{} ({})", name, terminal.name),
+                executable: terminal.executable.clone(),
+                args,
+                working_dir: None,
+                env: BTreeMap::new(),
+            });
+        }
+    }
+
+    for entry in desktop_launchers(env) {
+        if !is_duplicate(&launchers, &entry) {
+            launchers.push(entry);
+        }
+    }
+    launchers
+}
+
+/// 标识相同，或可执行文件名相同（如 `code.desktop` 中的 `/usr/share/code/code`）的视为同一应用
+fn is_duplicate(launchers: &[Launcher], candidate: &Launcher) -> bool {
+    let stem = |launcher: &Launcher| {
+        Path::new(&launcher.executable)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_lowercase())
+    };
+    let candidate_stem = stem(candidate);
+    launchers.iter().any(|launcher| {
+        launcher.id == candidate.id
+            || (launcher.args.first().map(String::as_str) != Some("-a")
+                && stem(launcher) == candidate_stem)
+    })
+}
+
+/// 读取 XDG 应用目录中的编辑器与终端；同名 `.desktop` 文件以先出现的目录为准
+fn desktop_launchers(env: &DetectEnv) -> Vec<Launcher> {
+    let mut seen = HashSet::new();
+    let mut launchers = Vec::new();
+    for dir in &env.data_dirs {
+        let Ok(entries) = fs::read_dir(dir.join("applications")) else {
+            continue;
+        };
+        let mut files: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "desktop"))
+            .collect();
+        files.sort();
+        for file in files {
+            let Some(file_name) = file.file_name().map(|name| name.to_os_string()) else {
+                continue;
+            };
+            if !seen.insert(file_name) {
+                continue;
+            }
+            if let Some(launcher) = fs::read_to_string(&file)
+                .ok()
+                .and_then(|content| parse_desktop_entry(&file, &content, env))
+            {
+                launchers.push(launcher);
+            }
+        }
+    }
+    launchers
+}
+
+/// 解析 `.desktop` 文件，只接受可显示的编辑器/IDE 与终端应用
+fn parse_desktop_entry(file: &Path, content: &str, env: &DetectEnv) -> Option<Launcher> {
+    let mut fields: BTreeMap<&str, &str> = BTreeMap::new();
+    let mut in_entry = false;
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_entry = line == "[Desktop Entry]";
+            continue;
+        }
+        if !in_entry || line.starts_with('#') {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            fields.entry(key.trim()).or_insert(value.trim());
+        }
+    }
+
+    let is_true = |key: &str| fields.get(key).is_some_and(|value| *value == "true");
+    if fields.get("Type") != Some(&"Application")
+        || is_true("NoDisplay")
+        || is_true("Hidden")
+        || is_true("Terminal")
+    {
+        return None;
+    }
+    let categories: Vec<&str> = fields.get("Categories")?.split(';').collect();
+    let is_terminal = categories.contains(&TERMINAL_CATEGORY);
+    let is_editor = categories
+        .iter()
+        .any(|category| EDITOR_CATEGORIES.contains(category));
+    if !is_terminal && !is_editor {
+        return None;
+    }
+
+    let name = fields.get("Name")?;
+    let mut tokens = split_exec(fields.get("Exec")?).into_iter();
+    let program = tokens.next()?;
+    let executable = if Path::new(&program).is_absolute() {
+        Some(PathBuf::from(&program)).filter(|path| path.is_file())
+    } else {
+        env.find_command(&program)
+    }?;
+
+    // 文件/URL 字段替换为工作区路径，其余字段代码丢弃
+    let mut args = Vec::new();
+    let mut takes_path = false;
+    for token in tokens {
+        match token.as_str() {
+            "%f" | "%F" | "%u" | "%U" => {
+                takes_path = true;
+                args.push(PATH_PLACEHOLDER.to_string());
+            }
+            code if code.len() == 2 && code.starts_with('%') => {}
+            _ => args.push(token.replace("%%", "%")),
+        }
+    }
+    // 编辑器不接受文件参数时追加路径；终端在工作区目录中启动即可
+    if is_editor && !is_terminal && !takes_path {
+        args.push(PATH_PLACEHOLDER.to_string());
+    }
+
+    let stem = file.file_stem()?.to_string_lossy();
+    Some(Launcher {
+        id: slugify(&stem),
+        name: name.to_string(),
+        executable: executable.to_string_lossy().to_string(),
+        args,
+        working_dir: None,
+        env: BTreeMap::new(),
+    })
+}
+
+/// 按 `.desktop` 规范拆分 Exec：空白分隔，双引号内可用反斜杠转义 `"`、`` ` ``、`$`、`\`
+///
+/// 其他反斜杠原样保留，因此同样用于拆分设置中的终端命令（如带引号的 Windows 路径）。
+pub(crate) fn split_exec(exec: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut has_token = false;
+    let mut chars = exec.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                has_token = true;
+            }
+            '\\' if in_quotes => {
+                let escaped = chars.next_if(|next| matches!(next, '"' | '`' | '$' | '\\'));
+                current.push(escaped.unwrap_or('\\'));
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if has_token {
+                    tokens.push(std::mem::take(&mut current));
+                    has_token = false;
+                }
+            }
+            c => {
+                current.push(c);
+                has_token = true;
+            }
+        }
+    }
+    if has_token {
+        tokens.push(current);
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn touch(path: &Path) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "").unwrap();
+    }
+
+    fn linux_env(temp_dir: &TempDir) -> DetectEnv {
+        DetectEnv {
+            os: Os::Linux,
+            path_dirs: vec![temp_dir.path().join("bin")],
+            home: Some(temp_dir.path().join("home")),
+            data_dirs: vec![temp_dir.path().join("share")],
+        }
+    }
+
+    fn ids(launchers: &[Launcher]) -> Vec<&str> {
+        launchers.iter().map(|l| l.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_detect_path_locations_and_terminal_editors() {
+        let temp_dir = TempDir::new().unwrap();
+        let bin = temp_dir.path().join("bin");
+        for command in ["code", "kitty", "nvim"] {
+            touch(&bin.join(command));
+        }
+        // JetBrains Toolbox 脚本位于标准安装位置而不在 PATH 中
+        let idea = temp_dir
+            .path()
+            .join("home/.local/share/JetBrains/Toolbox/scripts/idea");
+        touch(&idea);
+
+        let launchers = detect(&linux_env(&temp_dir));
+        assert_eq!(
+            ids(&launchers),
+            vec!["vscode", "idea", "kitty", "neovim-kitty"]
+        );
+        assert_eq!(launchers[0].args, vec!["{path}"]);
+        assert_eq!(launchers[1].executable, idea.to_string_lossy());
+        assert_eq!(launchers[2].args, vec!["--directory", "{path}"]);
+
+        let neovim = &launchers[3];
+        assert_eq!(neovim.name, "Neovim (kitty)");
+        assert_eq!(neovim.executable, bin.join("kitty").to_string_lossy());
+        assert_eq!(
+            neovim.args,
+            vec![
+                "--directory".to_string(),
+                "{path}".to_string(),
+                bin.join("nvim").to_string_lossy().to_string(),
+                "{path}".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detect_desktop_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let bin = temp_dir.path().join("bin");
+        touch(&bin.join("code"));
+        touch(&bin.join("gnome-text-editor"));
+        touch(&bin.join("foot"));
+        touch(&bin.join("htop"));
+        let applications = temp_dir.path().join("share/applications");
+        fs::create_dir_all(&applications).unwrap();
+        let write =
+            |name: &str, content: &str| fs::write(applications.join(name), content).unwrap();
+        write(
+            "org.gnome.TextEditor.desktop",
+            "[Desktop Entry]\nType=Application\nName=Text Editor\nExec=gnome-text-editor %U\nCategories=GNOME;GTK;Utility;TextEditor;\n",
+        );
+        write(
+            "foot.desktop",
+            "[Desktop Entry]\nType=Application\nName=Foot\nExec=foot\nCategories=System;TerminalEmulator;\n",
+        );
+        // 与 PATH 中已探测到的 VS Code 重复
+        write(
+            "code.desktop",
+            "[Desktop Entry]\nType=Application\nName=Visual Studio Code\nExec=code --unity-launch %F\nCategories=Development;IDE;\n",
+        );
+        // 终端内运行的应用、隐藏的应用与无关分类都被忽略
+        write(
+            "htop.desktop",
+            "[Desktop Entry]\nType=Application\nName=htop\nExec=htop\nTerminal=true\nCategories=System;Monitor;\n",
+        );
+        write(
+            "missing.desktop",
+            "[Desktop Entry]\nType=Application\nName=Missing\nExec=\"/nonexistent/editor\" %f\nCategories=TextEditor;\n",
+        );
+
+        let launchers = detect(&linux_env(&temp_dir));
+        assert_eq!(
+            ids(&launchers),
+            vec!["vscode", "foot", "org-gnome-texteditor"]
+        );
+        assert_eq!(launchers[1].name, "Foot");
+        assert!(launchers[1].args.is_empty());
+        assert_eq!(launchers[2].args, vec!["{path}"]);
+    }
+
+    #[test]
+    fn test_split_exec() {
+        assert_eq!(
+            split_exec(r#""/opt/My Editor/bin/edit" --flag "a \"b\"" %F"#),
+            vec!["/opt/My Editor/bin/edit", "--flag", "a \"b\"", "%F"]
+        );
+        assert_eq!(split_exec("  code   "), vec!["code"]);
+        // 引号内的普通反斜杠（Windows 路径）原样保留
+        assert_eq!(
+            split_exec(r#""C:\Program Files\Alacritty\alacritty.exe" --working-directory {path}"#),
+            vec![
+                r"C:\Program Files\Alacritty\alacritty.exe",
+                "--working-directory",
+                "{path}"
+            ]
+        );
+        assert_eq!(split_exec(r#"app """#), vec!["app", ""]);
+    }
+}
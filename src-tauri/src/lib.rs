@@ -1,23 +1,31 @@
 //! YTools 核心库
 //! 
 //! 模块化组织：
+//! - launchers: 启动器（用 IDE、终端等打开工作区）
 //! - notes: 笔记管理功能
 //! - search: 搜索算法和工作区搜索
 //! - shortcuts: 快捷键解析和管理
 //! - system: 系统集成（托盘、窗口、目录打开）
 
+mod launchers;
 mod notes;
 mod search;
 mod shortcuts;
 mod system;
 
 // 重新导出模块中的公开函数，供 Tauri 命令使用
+pub use launchers::{
+    add_launcher, detect_launchers, invoke_launcher, list_launchers, remove_launcher, LauncherStore,
+};
 pub use notes::{
     create_note, delete_note_file, import_note, list_notes, read_note, save_note,
 };
-pub use search::{open_folder, search_workspaces};
+pub use search::{
+    cancel_search, open_file, open_folder, search_content, search_files, search_workspaces,
+    start_search, FileWalks, FrecencyStore, SearchTasks, WorkspaceIndex,
+};
 pub use shortcuts::update_global_shortcuts;
-pub use system::open_directory;
+pub use system::{open_directory, open_terminal};
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
@@ -36,17 +44,33 @@ pub fn run() {
             tauri_plugin_autostart::MacosLauncher::LaunchAgent,
             None,
         ))
+        .manage(WorkspaceIndex::new())
+        .manage(FrecencyStore::load_default())
+        .manage(FileWalks::new())
+        .manage(SearchTasks::new())
+        .manage(LauncherStore::load_default())
         .invoke_handler(tauri::generate_handler![
             greet,
             read_note,
             save_note,
             list_notes,
             search_workspaces,
+            search_files,
+            start_search,
+            search_content,
+            cancel_search,
             open_folder,
+            open_file,
+            list_launchers,
+            detect_launchers,
+            add_launcher,
+            remove_launcher,
+            invoke_launcher,
             import_note,
             create_note,
             delete_note_file,
             open_directory,
+            open_terminal,
             update_global_shortcuts
         ])
         .setup(|app| {
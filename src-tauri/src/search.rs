@@ -1,342 +1,589 @@
 //! 搜索模块
-//! 
+//!
 //! 提供工作区搜索功能，包括拼音匹配、模糊匹配等算法
 
-use pinyin::ToPinyinMulti;
+mod content;
+mod discovery;
+mod files;
+mod frecency;
+mod fuzzy_pinyin;
+mod git;
+mod index;
+mod project;
+mod query;
+mod roots;
+mod scoring;
+mod stream;
+mod subsequence;
+mod syllables;
+mod tasks;
+mod traditional;
+mod transliterate;
+
+pub use content::search_content;
+pub use discovery::{SearchDirectory, SearchDirectoryInput};
+pub use files::{open_file, search_files, FileWalks};
+pub use frecency::FrecencyStore;
+pub use fuzzy_pinyin::FuzzyPinyinRules;
+pub use git::GitStatus;
+pub use index::WorkspaceIndex;
+pub use project::{ProjectInfo, ProjectType};
+pub use roots::RootStatus;
+pub use stream::start_search;
+pub use tasks::{cancel_search, SearchTasks};
+
+use crate::system::open_directory;
+use discovery::into_directories;
+use frecency::frecency_bonus;
+use git::GIT_STATUS_EVENT;
+use index::IndexedWorkspace;
+use query::ParsedQuery;
+use roots::DEFAULT_ROOT_TIMEOUT_MS;
+use scoring::{merge_ranges, score_entry, PreparedQuery};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::Emitter;
+use transliterate::pinyin_syllables;
+use unicode_normalization::UnicodeNormalization;
+
+/// 命中的匹配方式（按基础得分从高到低排列）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MatchKind {
+    /// 名称直接包含查询（忽略大小写）
+    Literal,
+    /// 去除分隔符后包含查询
+    Normalized,
+    /// 拼音全拼包含查询（支持多音字、繁体字与假名罗马字）
+    PinyinFull,
+    /// 拼音首字母包含查询（支持多音字、繁体字与假名罗马字）
+    PinyinInitials,
+    /// 全拼、部分音节与首字母混合输入（如 `xiangmgl`）
+    PinyinMixed,
+    /// 子序列匹配（查询字符按顺序出现在名称中，fzf 风格）
+    Subsequence,
+    /// 模糊匹配（少量拼写错误或拼音近似）
+    Fuzzy,
+}
+
+/// 工作区搜索结果
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceHit {
+    /// 工作区文件夹（文件搜索时为文件）的绝对路径
+    pub path: String,
+    /// 所属的搜索目录（与配置中的写法一致）
+    pub root: String,
+    /// 显示名称（文件夹名或文件名）
+    pub name: String,
+    /// 命中的匹配方式，查询为空（或只有操作符）时为 None
+    pub match_kind: Option<MatchKind>,
+    /// 匹配得分，越高越相关
+    pub score: i64,
+    /// 名称中与查询对应的字符区间，供界面高亮（拼音匹配会映射回对应的中文字符）
+    pub highlights: Vec<MatchRange>,
+    /// 项目类型、构建工具、主要语言与修改时间（文件搜索时为空）
+    #[serde(flatten)]
+    pub project: ProjectInfo,
+    /// Git 仓库状态，不是 Git 仓库（或文件搜索）时为空
+    pub git: Option<GitStatus>,
+}
+
+/// 名称中的字符区间 `[start, end)`，按 Unicode 字符计数（前端可用 `Array.from(name)` 切分）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchRange {
+    pub start: usize,
+    pub end: usize,
+}
 
-/// 规范化字符串：转小写并移除常见分隔符，便于宽松匹配
+/// 规范化字符串，便于宽松匹配
+///
+/// 依次进行兼容分解（全角字母数字折叠为半角）、去除拉丁字母的附加符号（`é` → `e`）、
+/// 重新组合（NFKC，假名浊点、谚文等不受影响）、大小写折叠，最后移除分隔符等非字母数字字符。
+/// 汉字等非拉丁文字原样保留。
 pub(crate) fn normalize(input: &str) -> String {
     input
-        .chars()
-        .filter(|c| c.is_ascii_alphanumeric())
-        .collect::<String>()
+        .nfkd()
+        .filter(|c| !is_diacritic(*c))
+        .nfc()
+        .flat_map(char::to_lowercase)
+        .filter(|c| c.is_alphanumeric())
+        .collect()
+}
+
+/// 组合用附加符号（重音、分音符、软音符等）
+fn is_diacritic(c: char) -> bool {
+    matches!(c, '\u{0300}'..='\u{036F}')
+}
+
+/// 拼音形式（全拼或首字母）及其与原名称的字符对应关系
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct PinyinVariant {
+    /// 拼音文本（小写）
+    pub text: String,
+    /// `text` 中每个字符来源于原名称的第几个字符，用于把匹配位置映射回中文
+    pub sources: Vec<usize>,
+}
+
+impl PinyinVariant {
+    fn push(&mut self, c: char, source: usize) {
+        self.text.push(c);
+        self.sources.push(source);
+    }
+
+    fn push_str(&mut self, s: &str, source: usize) {
+        for c in s.chars() {
+            self.push(c, source);
+        }
+    }
+}
+
+/// 多音字读音组合数的上限，超出后其余多音字只取第一个（最常用的）读音
+pub(crate) const MAX_PINYIN_VARIANTS: usize = 16;
+
+/// 取多音字参与组合的读音，保证组合总数不超过 `MAX_PINYIN_VARIANTS`
+fn bounded_readings(
+    syllables: Vec<&'static str>,
+    combinations: usize,
+) -> impl Iterator<Item = &'static str> {
+    let limit = if combinations * syllables.len() > MAX_PINYIN_VARIANTS {
+        1
+    } else {
+        syllables.len()
+    };
+    syllables.into_iter().take(limit)
 }
 
 /// 将中文字符串转换为拼音全拼（小写，无分隔符）
-/// 为了处理多音字，返回多个可能的拼音组合
-fn to_pinyin_full_multi(text: &str) -> Vec<String> {
-    let mut current_results = vec![String::new()];
+/// 为了处理多音字，返回多个可能的拼音组合（数量有上限，完整匹配由读音网格负责）
+fn to_pinyin_full_multi(text: &str) -> Vec<PinyinVariant> {
+    let mut current_results = vec![PinyinVariant::default()];
 
-    for c in text.chars() {
-        if c.is_ascii_alphabetic() {
-            // ASCII 字母保留（转小写）
-            let lower = c.to_lowercase().to_string();
+    for (index, c) in text.chars().enumerate() {
+        if c.is_ascii() {
+            // ASCII 字母转小写，其他 ASCII 字符原样保留
             for result in current_results.iter_mut() {
-                result.push_str(&lower);
-            }
-        } else if c.is_ascii() {
-            // 其他 ASCII 字符保留
-            for result in current_results.iter_mut() {
-                result.push(c);
+                result.push(c.to_ascii_lowercase(), index);
             }
         } else {
-            // 中文字符：获取所有可能的拼音（使用 ToPinyinMulti）
-            if let Some(pinyin_multi) = c.to_pinyin_multi() {
-                let mut new_results = Vec::new();
-                for pinyin_option in pinyin_multi {
-                    for base in &current_results {
-                        let mut new_result = base.clone();
-                        new_result.push_str(pinyin_option.plain());
-                        new_results.push(new_result);
-                    }
-                }
-                if !new_results.is_empty() {
-                    current_results = new_results;
+            // 中文字符：获取所有可能的拼音（含繁体字对应简体字的读音）
+            let mut new_results = Vec::new();
+            for syllable in bounded_readings(pinyin_syllables(c), current_results.len()) {
+                for base in &current_results {
+                    let mut new_result = base.clone();
+                    new_result.push_str(syllable, index);
+                    new_results.push(new_result);
                 }
             }
+            if !new_results.is_empty() {
+                current_results = new_results;
+            }
         }
     }
 
     current_results
-        .into_iter()
-        .map(|s| s.to_lowercase())
-        .collect()
 }
 
 /// 将中文字符串转换为拼音首字母缩写（小写）
-/// 为了处理多音字，返回多个可能的首字母组合
-fn to_pinyin_initials_multi(text: &str) -> Vec<String> {
-    let mut current_results = vec![String::new()];
+/// 为了处理多音字，返回多个可能的首字母组合（数量有上限）
+fn to_pinyin_initials_multi(text: &str) -> Vec<PinyinVariant> {
+    let mut current_results = vec![PinyinVariant::default()];
 
-    for c in text.chars() {
+    for (index, c) in text.chars().enumerate() {
         if c.is_ascii_alphabetic() {
             // ASCII 字母保留（转小写）
-            let lower = c.to_lowercase().to_string();
             for result in current_results.iter_mut() {
-                result.push_str(&lower);
+                result.push(c.to_ascii_lowercase(), index);
             }
         } else if c.is_ascii() {
             // 跳过其他 ASCII 字符
             continue;
         } else {
-            // 中文字符：获取所有可能的拼音首字母（使用 ToPinyinMulti）
-            if let Some(pinyin_multi) = c.to_pinyin_multi() {
-                let mut new_results = Vec::new();
-                for pinyin_option in pinyin_multi {
-                    if let Some(first_char) = pinyin_option.plain().chars().next() {
-                        for base in &current_results {
-                            let mut new_result = base.clone();
-                            new_result.push(first_char);
-                            new_results.push(new_result);
-                        }
+            // 中文字符：获取所有可能的拼音首字母（含繁体字对应简体字的读音）
+            let mut new_results = Vec::new();
+            for syllable in bounded_readings(pinyin_syllables(c), current_results.len()) {
+                if let Some(first_char) = syllable.chars().next() {
+                    for base in &current_results {
+                        let mut new_result = base.clone();
+                        new_result.push(first_char, index);
+                        new_results.push(new_result);
                     }
                 }
-                if !new_results.is_empty() {
-                    current_results = new_results;
-                }
+            }
+            if !new_results.is_empty() {
+                current_results = new_results;
             }
         }
     }
 
     current_results
-        .into_iter()
-        .map(|s| s.to_lowercase())
-        .collect()
 }
 
-/// 最长公共子串长度（连续匹配长度）
-fn longest_common_substring_len(a: &str, b: &str) -> usize {
-    if a.is_empty() || b.is_empty() {
-        return 0;
-    }
-    let ac: Vec<char> = a.chars().collect();
-    let bc: Vec<char> = b.chars().collect();
-    let mut dp = vec![0usize; bc.len() + 1];
-    let mut best = 0usize;
-    for i in 1..=ac.len() {
-        let mut prev = 0usize;
-        for j in 1..=bc.len() {
-            let tmp = dp[j];
-            if ac[i - 1] == bc[j - 1] {
-                dp[j] = prev + 1;
-                if dp[j] > best {
-                    best = dp[j];
-                }
-            } else {
-                dp[j] = 0;
-            }
-            prev = tmp;
-        }
-    }
-    best
+/// 默认最多返回的搜索结果数
+pub const DEFAULT_RESULT_LIMIT: usize = 50;
+
+/// 搜索选项
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SearchOptions {
+    /// 最多返回的结果数，为空时使用默认值，0 表示不限制
+    pub limit: Option<usize>,
+    /// 模糊拼音规则（如 zh/z、in/ing），默认全部关闭
+    pub fuzzy_pinyin: FuzzyPinyinRules,
+    /// 单个搜索目录的遍历超时（毫秒），为空时使用默认值
+    pub root_timeout_ms: Option<u64>,
+    /// 只返回这些类型的项目，为空时不筛选
+    pub project_types: Vec<ProjectType>,
 }
 
-/// 最长公共子序列长度（非连续匹配总量）
-fn longest_common_subsequence_len(a: &str, b: &str) -> usize {
-    if a.is_empty() || b.is_empty() {
-        return 0;
-    }
-    let ac: Vec<char> = a.chars().collect();
-    let bc: Vec<char> = b.chars().collect();
-    let mut prev = vec![0usize; bc.len() + 1];
-    let mut curr = vec![0usize; bc.len() + 1];
-    for i in 1..=ac.len() {
-        for j in 1..=bc.len() {
-            curr[j] = if ac[i - 1] == bc[j - 1] {
-                prev[j - 1] + 1
-            } else {
-                prev[j].max(curr[j - 1])
-            };
+impl SearchOptions {
+    /// 实际使用的结果数上限
+    pub(crate) fn effective_limit(&self) -> usize {
+        match self.limit {
+            None => DEFAULT_RESULT_LIMIT,
+            Some(0) => usize::MAX,
+            Some(limit) => limit,
         }
-        std::mem::swap(&mut prev, &mut curr);
     }
-    prev[bc.len()]
-}
 
-/// Levenshtein 编辑距离（大小写与分隔符已通过 normalize 处理在外部）
-fn levenshtein_distance(a: &str, b: &str) -> usize {
-    let ac: Vec<char> = a.chars().collect();
-    let bc: Vec<char> = b.chars().collect();
-    let n = ac.len();
-    let m = bc.len();
-    if n == 0 {
-        return m;
-    }
-    if m == 0 {
-        return n;
-    }
-
-    let mut prev: Vec<usize> = (0..=m).collect();
-    let mut curr: Vec<usize> = vec![0; m + 1];
-    for i in 1..=n {
-        curr[0] = i;
-        for j in 1..=m {
-            let cost = if ac[i - 1] == bc[j - 1] { 0 } else { 1 };
-            curr[j] = (prev[j] + 1) // 删除
-                .min(curr[j - 1] + 1) // 插入
-                .min(prev[j - 1] + cost); // 替换
-        }
-        std::mem::swap(&mut prev, &mut curr);
+    /// 实际使用的单个搜索目录遍历超时
+    pub(crate) fn root_timeout(&self) -> Duration {
+        Duration::from_millis(self.root_timeout_ms.unwrap_or(DEFAULT_ROOT_TIMEOUT_MS))
     }
-    prev[m]
+}
+
+/// 搜索结果及各搜索目录的状态
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResults {
+    /// 排序后的结果
+    pub hits: Vec<WorkspaceHit>,
+    /// 各搜索目录的状态（顺序与配置一致），不存在、无权限或超时的目录结果为空
+    pub roots: Vec<RootStatus>,
 }
 
 /// 搜索工作区文件夹
+///
+/// 查询支持 `@别名`、`-排除词`、`"短语"`、`^前缀`、`后缀$` 等操作符，见 `query` 模块。
 #[tauri::command]
-pub fn search_workspaces(query: String, directories: Vec<String>) -> Result<Vec<String>, String> {
+pub fn search_workspaces(
+    app: tauri::AppHandle,
+    index: tauri::State<'_, WorkspaceIndex>,
+    frecency: tauri::State<'_, FrecencyStore>,
+    query: String,
+    directories: Vec<SearchDirectoryInput>,
+    options: Option<SearchOptions>,
+) -> Result<SearchResults, String> {
+    let results = search_index(
+        &index,
+        &frecency,
+        &query,
+        &into_directories(directories),
+        &options.unwrap_or_default(),
+    )?;
+    refresh_git_status(&app, &index, &results.hits);
+    Ok(results)
+}
+
+/// 基于索引执行工作区搜索，并融合打开记录（frecency）排序
+///
+/// 每个候选由所有匹配器统一评分（见 `scoring` 模块），再叠加 frecency 加分后整体排序；
+/// 空查询时返回全部工作区，最近/最常打开的排在最前。
+pub(crate) fn search_index(
+    index: &WorkspaceIndex,
+    frecency: &FrecencyStore,
+    query: &str,
+    directories: &[SearchDirectory],
+    options: &SearchOptions,
+) -> Result<SearchResults, String> {
     // 如果没有配置搜索目录，返回空结果
     if directories.is_empty() {
-        return Ok(Vec::new());
+        return Ok(SearchResults::default());
     }
 
-    // 当查询为空时，直接返回全部目录（维持现有行为）
-    let query_lower = query.to_lowercase();
-    let query_norm = normalize(&query_lower);
-    let mut results = Vec::new();
-    let mut all_dirs: Vec<String> = Vec::new();
+    // 遍历所有配置的搜索目录下发现的工作区（不存在、无权限或超时的目录没有条目）
+    let snapshot = index.snapshot(directories, options.root_timeout());
+    let roots = snapshot
+        .iter()
+        .map(|(root, entries)| RootStatus::from_result(root, entries, |entries| entries.len()))
+        .collect();
+    let candidates = directories
+        .iter()
+        .zip(&snapshot)
+        .filter_map(|(dir, (_, entries))| {
+            Some(entries.as_ref().ok()?.iter().map(move |entry| (dir, entry)))
+        })
+        .flatten();
+    let scored = score_candidates(&frecency.scores(), query, options, candidates);
+    Ok(SearchResults {
+        hits: with_git_status(index, sort_hits(scored, options.effective_limit())),
+        roots,
+    })
+}
 
-    // 遍历所有配置的搜索目录
-    for workspace_dir_str in directories {
-        let workspace_dir = PathBuf::from(&workspace_dir_str);
+/// 对候选逐个评分，工作区搜索与文件搜索共用，返回未排序的 (frecency, 结果)
+///
+/// `candidates` 为 (所属搜索目录, 条目)，先按查询操作符过滤，再由匹配器打分并叠加 frecency 加分。
+pub(crate) fn score_candidates<'a>(
+    frecency_scores: &HashMap<String, i64>,
+    query: &str,
+    options: &SearchOptions,
+    candidates: impl Iterator<Item = (&'a SearchDirectory, &'a IndexedWorkspace)>,
+) -> Vec<(i64, WorkspaceHit)> {
+    let frecency_of = |path: &str| frecency_scores.get(path).copied().unwrap_or(0);
+    let parsed = ParsedQuery::parse(query);
+    let query = PreparedQuery::new(&parsed.text).with_fuzzy_pinyin(&options.fuzzy_pinyin);
+    let mut results: Vec<(i64, WorkspaceHit)> = Vec::new();
 
-        if !workspace_dir.exists() {
+    for (dir, entry) in candidates {
+        // @别名 之外的搜索目录、类型不符的项目，以及排除词、短语与锚点条件不满足的直接跳过
+        if !parsed.includes_root(dir) || !entry.project.matches_types(&options.project_types) {
+            continue;
+        }
+        let Some(filter_positions) = parsed.filter(entry) else {
+            continue;
+        };
+        let root = &dir.path;
+        let frecency = frecency_of(&entry.path);
+        let bonus = frecency_bonus(frecency);
+        if query.is_empty() {
+            let highlights = merge_ranges(filter_positions);
+            results.push((frecency, make_hit(root, entry, None, bonus, highlights)));
             continue;
         }
 
-        if let Ok(entries) = fs::read_dir(workspace_dir) {
-            for entry in entries.flatten() {
-                if let Ok(metadata) = entry.metadata() {
-                    if metadata.is_dir() {
-                        if let Ok(dir_name) = entry.file_name().into_string() {
-                            let name_lower = dir_name.to_lowercase();
-                            let name_norm = normalize(&name_lower);
-
-                            // 生成拼音表示（多音字版本）
-                            let name_pinyin_full_multi = to_pinyin_full_multi(&dir_name);
-                            let name_pinyin_initials_multi = to_pinyin_initials_multi(&dir_name);
-
-                            // 检查是否有任何拼音版本匹配
-                            let pinyin_full_match = name_pinyin_full_multi
-                                .iter()
-                                .any(|py| py.contains(&query_lower));
-                            let pinyin_initials_match = name_pinyin_initials_multi
-                                .iter()
-                                .any(|py| py.contains(&query_lower));
-
-                            // 记录全量目录，用于回退模糊匹配
-                            all_dirs.push(dir_name.clone());
-
-                            // 先尝试包含匹配（兼容去除分隔符后的包含 + 多音字拼音匹配）
-                            if query.is_empty()
-                                || name_lower.contains(&query_lower)
-                                || (!query_norm.is_empty() && name_norm.contains(&query_norm))
-                                || pinyin_full_match // 拼音全拼匹配（多音字）
-                                || pinyin_initials_match
-                            // 拼音首字母匹配（多音字）
-                            {
-                                results.push(dir_name);
-                            }
-                        }
-                    }
-                }
-            }
+        if let Some(scored) = score_entry(&query, entry) {
+            let score = scored.score + bonus;
+            let positions = scored
+                .highlights
+                .iter()
+                .flat_map(|range| range.start..range.end)
+                .chain(filter_positions)
+                .collect();
+            let hit = make_hit(
+                root,
+                entry,
+                Some(scored.kind),
+                score,
+                merge_ranges(positions),
+            );
+            results.push((frecency, hit));
         }
     }
+    results
+}
 
-    // 如果包含匹配已经有结果，按字典序返回
-    if !results.is_empty() || query.is_empty() {
-        results.sort();
-        return Ok(results);
-    }
-
-    // 回退：基于最长连续匹配（最长公共子串）、总匹配字符数（LCS）与编辑距离进行排序，取前5
-    // 同时考虑拼音匹配的得分（支持多音字）
-    let mut scored: Vec<(String, usize, usize, usize)> = Vec::new();
-    for name in all_dirs.into_iter() {
-        let name_lower = name.to_lowercase();
-        let name_norm = normalize(&name_lower);
-        let name_pinyin_full_multi = to_pinyin_full_multi(&name);
-        let name_pinyin_initials_multi = to_pinyin_initials_multi(&name);
-
-        // 计算原始名称匹配得分
-        let lcs_sub = longest_common_substring_len(&name_norm, &query_norm);
-        let lcs_seq = longest_common_subsequence_len(&name_norm, &query_norm);
-        let edit = levenshtein_distance(&name_norm, &query_norm);
-
-        // 计算所有拼音全拼的最佳匹配得分
-        let mut best_pinyin_lcs_sub = 0;
-        let mut best_pinyin_lcs_seq = 0;
-        let mut best_pinyin_edit = usize::MAX;
-        for pinyin in &name_pinyin_full_multi {
-            let lcs_sub = longest_common_substring_len(pinyin, &query_lower);
-            let lcs_seq = longest_common_subsequence_len(pinyin, &query_lower);
-            let edit = levenshtein_distance(pinyin, &query_lower);
-
-            best_pinyin_lcs_sub = best_pinyin_lcs_sub.max(lcs_sub);
-            best_pinyin_lcs_seq = best_pinyin_lcs_seq.max(lcs_seq);
-            best_pinyin_edit = best_pinyin_edit.min(edit);
-        }
+/// 按得分降序排序（同分时依次按 frecency 降序、名称、路径），并截取前 `limit` 个
+pub(crate) fn sort_hits(mut results: Vec<(i64, WorkspaceHit)>, limit: usize) -> Vec<WorkspaceHit> {
+    results.sort_by(|(frecency_a, a), (frecency_b, b)| {
+        b.score
+            .cmp(&a.score)
+            .then(frecency_b.cmp(frecency_a))
+            .then(a.name.cmp(&b.name))
+            .then(a.path.cmp(&b.path))
+    });
 
-        // 计算所有拼音首字母的最佳匹配得分
-        let mut best_initials_lcs_sub = 0;
-        let mut best_initials_lcs_seq = 0;
-        let mut best_initials_edit = usize::MAX;
-        for initials in &name_pinyin_initials_multi {
-            let lcs_sub = longest_common_substring_len(initials, &query_lower);
-            let lcs_seq = longest_common_subsequence_len(initials, &query_lower);
-            let edit = levenshtein_distance(initials, &query_lower);
-
-            best_initials_lcs_sub = best_initials_lcs_sub.max(lcs_sub);
-            best_initials_lcs_seq = best_initials_lcs_seq.max(lcs_seq);
-            best_initials_edit = best_initials_edit.min(edit);
-        }
+    results
+        .into_iter()
+        .map(|(_, hit)| hit)
+        .take(limit)
+        .collect()
+}
 
-        // 取所有匹配方式中的最佳得分
-        let best_lcs_sub = lcs_sub.max(best_pinyin_lcs_sub).max(best_initials_lcs_sub);
-        let best_lcs_seq = lcs_seq.max(best_pinyin_lcs_seq).max(best_initials_lcs_seq);
-        let best_edit = edit.min(best_pinyin_edit).min(best_initials_edit);
+/// 由索引条目构造搜索结果
+fn make_hit(
+    root: &str,
+    entry: &IndexedWorkspace,
+    match_kind: Option<MatchKind>,
+    score: i64,
+    highlights: Vec<MatchRange>,
+) -> WorkspaceHit {
+    WorkspaceHit {
+        path: entry.path.clone(),
+        root: root.to_string(),
+        name: entry.name.clone(),
+        match_kind,
+        score,
+        highlights,
+        project: entry.project.clone(),
+        git: None,
+    }
+}
 
-        scored.push((name, best_lcs_sub, best_lcs_seq, best_edit));
+/// 为工作区搜索结果补充缓存中的 Git 状态，不读取仓库；缺失或过期的由 `refresh_git_status` 在后台读取
+pub(crate) fn with_git_status(
+    index: &WorkspaceIndex,
+    mut hits: Vec<WorkspaceHit>,
+) -> Vec<WorkspaceHit> {
+    for hit in &mut hits {
+        hit.git = index.git_status(&hit.path);
     }
+    hits
+}
 
-    scored.sort_by(|a, b| {
-        // 优先：最长连续匹配降序，其次：总匹配字符数降序，其次：编辑距离升序，最后：名称字典序
-        b.1.cmp(&a.1)
-            .then(b.2.cmp(&a.2))
-            .then(a.3.cmp(&b.3))
-            .then(a.0.cmp(&b.0))
+/// 在后台读取结果中缺失或过期的 Git 状态，完成后推送 `git-status` 事件
+pub(crate) fn refresh_git_status(
+    app: &tauri::AppHandle,
+    index: &WorkspaceIndex,
+    hits: &[WorkspaceHit],
+) {
+    let app = app.clone();
+    index.refresh_git_status(hits.iter().map(|hit| hit.path.as_str()), move |updates| {
+        let _ = app.emit(GIT_STATUS_EVENT, updates);
     });
+}
 
-    let top5: Vec<String> = scored.into_iter().take(5).map(|t| t.0).collect();
-    Ok(top5)
+/// 搜索结果指向的路径类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HitKind {
+    /// 工作区文件夹
+    Directory,
+    /// 文件搜索的结果
+    File,
 }
 
-/// 打开文件夹（已废弃，使用 open_directory 代替）
-#[tauri::command]
-pub fn open_folder(folder_name: String) -> Result<(), String> {
-    let folder_path = PathBuf::from("D:\\workspaces").join(&folder_name);
+impl HitKind {
+    fn matches(self, path: &Path) -> bool {
+        match self {
+            Self::Directory => path.is_dir(),
+            Self::File => path.is_file(),
+        }
+    }
 
-    if !folder_path.exists() {
-        return Err(format!("文件夹不存在: {}", folder_name));
+    fn label(self) -> &'static str {
+        match self {
+            Self::Directory => "文件夹",
+            Self::File => "文件",
+        }
     }
+}
+
+/// 将搜索结果解析为已配置搜索目录下的实际路径
+///
+/// `target` 可以是搜索结果中的绝对路径，也可以是相对于搜索目录的文件夹名；
+/// 解析后的路径必须位于某个搜索目录之内，防止通过 `..` 或符号链接逃逸。
+pub(crate) fn resolve_workspace_path(
+    target: &str,
+    directories: &[SearchDirectory],
+) -> Result<PathBuf, String> {
+    resolve_under_roots(target, directories, HitKind::Directory)
+}
 
-    #[cfg(target_os = "windows")]
-    {
-        std::process::Command::new("explorer")
-            .arg(folder_path)
-            .spawn()
-            .map_err(|e| format!("打开文件夹失败: {}", e))?;
+/// 将文件搜索结果解析为搜索目录下的文件路径，规则与 [`resolve_workspace_path`] 相同
+pub(crate) fn resolve_file_path(
+    target: &str,
+    directories: &[SearchDirectory],
+) -> Result<PathBuf, String> {
+    resolve_under_roots(target, directories, HitKind::File)
+}
+
+fn resolve_under_roots(
+    target: &str,
+    directories: &[SearchDirectory],
+    kind: HitKind,
+) -> Result<PathBuf, String> {
+    if target.trim().is_empty() {
+        return Err(format!("{}路径不能为空", kind.label()));
     }
 
-    #[cfg(not(target_os = "windows"))]
-    {
-        std::process::Command::new("open")
-            .arg(folder_path)
-            .spawn()
-            .map_err(|e| format!("打开文件夹失败: {}", e))?;
+    // 规范化所有存在的搜索目录，用于校验路径归属
+    let roots: Vec<PathBuf> = directories
+        .iter()
+        .filter_map(|dir| fs::canonicalize(&dir.path).ok())
+        .collect();
+    if roots.is_empty() {
+        return Err("未配置可用的搜索目录".to_string());
     }
 
+    let target_path = Path::new(target);
+    let candidates: Vec<PathBuf> = if target_path.is_absolute() {
+        vec![target_path.to_path_buf()]
+    } else {
+        directories
+            .iter()
+            .map(|dir| Path::new(&dir.path).join(target))
+            .collect()
+    };
+
+    let mut outside_root = false;
+    for candidate in candidates {
+        // 规范化失败说明路径不存在，继续尝试下一个搜索目录
+        let Ok(canonical) = fs::canonicalize(&candidate) else {
+            continue;
+        };
+        if !kind.matches(&canonical) {
+            continue;
+        }
+        if roots.iter().any(|root| canonical.starts_with(root)) {
+            // 返回未规范化的路径，避免 Windows 下 `\\?\` 前缀影响资源管理器
+            return Ok(candidate);
+        }
+        outside_root = true;
+    }
+
+    if outside_root {
+        Err(format!("路径不在已配置的搜索目录中: {}", target))
+    } else {
+        Err(format!("{}不存在: {}", kind.label(), target))
+    }
+}
+
+/// 将前端传入的搜索结果路径解析为搜索目录下的绝对路径（供打开文件夹与启动器使用）
+pub(crate) fn resolve_hit_path(
+    path: &str,
+    directories: Vec<SearchDirectoryInput>,
+) -> Result<String, String> {
+    let resolved = resolve_workspace_path(path, &into_directories(directories))?;
+    Ok(resolved.to_string_lossy().to_string())
+}
+
+/// 打开搜索结果对应的文件夹（使用系统文件管理器），并记录打开历史
+#[tauri::command]
+pub fn open_folder(
+    frecency: tauri::State<'_, FrecencyStore>,
+    path: String,
+    directories: Vec<SearchDirectoryInput>,
+) -> Result<(), String> {
+    let folder_path = resolve_hit_path(&path, directories)?;
+    open_directory(folder_path.clone())?;
+
+    frecency.record_open_best_effort(&folder_path);
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use roots::RootState;
     use std::fs;
     use tempfile::TempDir;
 
+    /// 使用全新索引执行搜索，便于测试
+    fn search_workspaces(
+        query: String,
+        directories: Vec<String>,
+    ) -> Result<Vec<WorkspaceHit>, String> {
+        let directories: Vec<SearchDirectory> =
+            directories.into_iter().map(SearchDirectory::from).collect();
+        search_index(
+            &WorkspaceIndex::new(),
+            &FrecencyStore::in_memory(),
+            &query,
+            &directories,
+            &SearchOptions::default(),
+        )
+        .map(|results| results.hits)
+    }
+
+    /// 提取结果中的显示名称，便于断言
+    fn hit_names(hits: &[WorkspaceHit]) -> Vec<String> {
+        hits.iter().map(|h| h.name.clone()).collect()
+    }
+
     #[test]
     fn test_normalize() {
         assert_eq!(normalize("Hello-World"), "helloworld");
@@ -345,12 +592,25 @@ mod tests {
         assert_eq!(normalize(""), "");
     }
 
+    #[test]
+    fn test_normalize_unicode() {
+        // 附加符号、全角字符与大小写折叠
+        assert_eq!(normalize("Café"), "cafe");
+        assert_eq!(normalize("Cafe\u{301}"), "cafe");
+        assert_eq!(normalize("ＡＢＣ"), "abc");
+        assert_eq!(normalize("Ｔｅｓｔ＿１２３"), "test123");
+        assert_eq!(normalize("Ñandú-Straße"), "nandustraße");
+        // 假名的浊点与谚文不会被拆开
+        assert_eq!(normalize("がっこう"), "がっこう");
+        assert_eq!(normalize("한국어"), "한국어");
+    }
+
     #[test]
     fn test_search_workspaces_empty_directories() {
         // 测试空目录列表
         let result = search_workspaces("test".to_string(), vec![]);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), Vec::<String>::new());
+        assert!(result.unwrap().is_empty());
     }
 
     #[test]
@@ -358,7 +618,7 @@ mod tests {
         // 创建临时目录结构
         let temp_dir = TempDir::new().unwrap();
         let workspace_dir = temp_dir.path();
-        
+
         // 创建测试目录
         fs::create_dir_all(workspace_dir.join("dir1")).unwrap();
         fs::create_dir_all(workspace_dir.join("dir2")).unwrap();
@@ -370,8 +630,7 @@ mod tests {
             vec![workspace_dir.to_string_lossy().to_string()],
         );
         assert!(result.is_ok());
-        let mut results = result.unwrap();
-        results.sort();
+        let results = hit_names(&result.unwrap());
         assert!(results.contains(&"dir1".to_string()));
         assert!(results.contains(&"dir2".to_string()));
         assert!(results.contains(&"dir3".to_string()));
@@ -382,7 +641,7 @@ mod tests {
         // 创建临时目录结构
         let temp_dir = TempDir::new().unwrap();
         let workspace_dir = temp_dir.path();
-        
+
         fs::create_dir_all(workspace_dir.join("my-project")).unwrap();
         fs::create_dir_all(workspace_dir.join("other-project")).unwrap();
 
@@ -394,7 +653,8 @@ mod tests {
         assert!(result.is_ok());
         let results = result.unwrap();
         assert_eq!(results.len(), 1);
-        assert_eq!(results[0], "my-project");
+        assert_eq!(results[0].name, "my-project");
+        assert_eq!(results[0].match_kind, Some(MatchKind::Literal));
     }
 
     #[test]
@@ -402,7 +662,7 @@ mod tests {
         // 创建临时目录结构
         let temp_dir = TempDir::new().unwrap();
         let workspace_dir = temp_dir.path();
-        
+
         fs::create_dir_all(workspace_dir.join("my-project-v2")).unwrap();
         fs::create_dir_all(workspace_dir.join("other-project")).unwrap();
 
@@ -412,7 +672,7 @@ mod tests {
             vec![workspace_dir.to_string_lossy().to_string()],
         );
         assert!(result.is_ok());
-        let results = result.unwrap();
+        let results = hit_names(&result.unwrap());
         assert!(results.contains(&"my-project-v2".to_string()));
     }
 
@@ -421,7 +681,7 @@ mod tests {
         // 创建临时目录结构
         let temp_dir = TempDir::new().unwrap();
         let workspace_dir = temp_dir.path();
-        
+
         fs::create_dir_all(workspace_dir.join("MyProject")).unwrap();
 
         // 测试大小写不敏感
@@ -430,19 +690,549 @@ mod tests {
             vec![workspace_dir.to_string_lossy().to_string()],
         );
         assert!(result.is_ok());
-        let results = result.unwrap();
+        let results = hit_names(&result.unwrap());
         assert!(results.contains(&"MyProject".to_string()));
     }
 
     #[test]
     fn test_search_workspaces_nonexistent_directory() {
         // 测试不存在的目录应该被跳过
-        let result = search_workspaces(
-            "test".to_string(),
-            vec!["/nonexistent/path".to_string()],
-        );
+        let result = search_workspaces("test".to_string(), vec!["/nonexistent/path".to_string()]);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), Vec::<String>::new());
+        assert!(result.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_search_workspaces_same_name_in_different_roots() {
+        // 两个搜索目录下存在同名文件夹时，应能通过路径和所属根目录区分
+        let root_a = TempDir::new().unwrap();
+        let root_b = TempDir::new().unwrap();
+        fs::create_dir_all(root_a.path().join("shared")).unwrap();
+        fs::create_dir_all(root_b.path().join("shared")).unwrap();
+
+        let dir_a = root_a.path().to_string_lossy().to_string();
+        let dir_b = root_b.path().to_string_lossy().to_string();
+        let results =
+            search_workspaces("shared".to_string(), vec![dir_a.clone(), dir_b.clone()]).unwrap();
+
+        assert_eq!(results.len(), 2);
+        let roots: Vec<&str> = results.iter().map(|h| h.root.as_str()).collect();
+        assert!(roots.contains(&dir_a.as_str()));
+        assert!(roots.contains(&dir_b.as_str()));
+        for hit in &results {
+            assert_eq!(hit.name, "shared");
+            assert_eq!(
+                PathBuf::from(&hit.path),
+                PathBuf::from(&hit.root).join("shared")
+            );
+        }
+    }
+
+    #[test]
+    fn test_search_workspaces_fuzzy_fallback_kind() {
+        // 没有包含匹配时回退到模糊匹配，并标记匹配方式
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_dir = temp_dir.path();
+        fs::create_dir_all(workspace_dir.join("ytools")).unwrap();
+
+        let results = search_workspaces(
+            "ytoolz".to_string(),
+            vec![workspace_dir.to_string_lossy().to_string()],
+        )
+        .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].match_kind, Some(MatchKind::Fuzzy));
     }
-}
 
+    #[test]
+    fn test_resolve_workspace_path_by_name() {
+        // 仅传入文件夹名时，在配置的搜索目录中查找
+        let root_a = TempDir::new().unwrap();
+        let root_b = TempDir::new().unwrap();
+        fs::create_dir_all(root_b.path().join("only-in-b")).unwrap();
+
+        let directories = vec![
+            SearchDirectory::new(root_a.path().to_string_lossy()),
+            SearchDirectory::new(root_b.path().to_string_lossy()),
+        ];
+        let resolved = resolve_workspace_path("only-in-b", &directories).unwrap();
+        assert_eq!(resolved, root_b.path().join("only-in-b"));
+    }
+
+    #[test]
+    fn test_resolve_workspace_path_absolute() {
+        // 搜索结果中的绝对路径应原样返回
+        let root = TempDir::new().unwrap();
+        let project = root.path().join("project");
+        fs::create_dir_all(&project).unwrap();
+
+        let directories = vec![SearchDirectory::new(root.path().to_string_lossy())];
+        let resolved = resolve_workspace_path(&project.to_string_lossy(), &directories).unwrap();
+        assert_eq!(resolved, project);
+    }
+
+    #[test]
+    fn test_resolve_workspace_path_outside_root() {
+        // 不在任何搜索目录下的绝对路径应被拒绝
+        let root = TempDir::new().unwrap();
+        let other = TempDir::new().unwrap();
+        fs::create_dir_all(other.path().join("secret")).unwrap();
+
+        let directories = vec![SearchDirectory::new(root.path().to_string_lossy())];
+        let result =
+            resolve_workspace_path(&other.path().join("secret").to_string_lossy(), &directories);
+        assert!(result.unwrap_err().contains("不在已配置的搜索目录中"));
+    }
+
+    #[test]
+    fn test_resolve_workspace_path_parent_traversal() {
+        // 通过 `..` 跳出搜索目录应被拒绝
+        let parent = TempDir::new().unwrap();
+        let root = parent.path().join("root");
+        fs::create_dir_all(&root).unwrap();
+        fs::create_dir_all(parent.path().join("sibling")).unwrap();
+
+        let directories = vec![SearchDirectory::new(root.to_string_lossy())];
+        let result = resolve_workspace_path("../sibling", &directories);
+        assert!(result.unwrap_err().contains("不在已配置的搜索目录中"));
+    }
+
+    #[test]
+    fn test_resolve_file_path() {
+        let root = TempDir::new().unwrap();
+        fs::create_dir_all(root.path().join("app/src")).unwrap();
+        fs::write(root.path().join("app/src/main.rs"), "").unwrap();
+        let directories = vec![SearchDirectory::new(root.path().to_string_lossy())];
+
+        let file = root.path().join("app/src/main.rs");
+        let resolved = resolve_file_path(&file.to_string_lossy(), &directories).unwrap();
+        assert_eq!(resolved, file);
+        // 文件与文件夹互不混用
+        assert!(resolve_file_path("app", &directories).is_err());
+        assert!(resolve_workspace_path(&file.to_string_lossy(), &directories).is_err());
+        let outside = TempDir::new().unwrap();
+        fs::write(outside.path().join("secret.txt"), "").unwrap();
+        let result = resolve_file_path(
+            &outside.path().join("secret.txt").to_string_lossy(),
+            &directories,
+        );
+        assert!(result.unwrap_err().contains("不在已配置的搜索目录中"));
+    }
+
+    #[test]
+    fn test_resolve_workspace_path_missing() {
+        // 不存在的文件夹返回明确的错误
+        let root = TempDir::new().unwrap();
+        let directories = vec![SearchDirectory::new(root.path().to_string_lossy())];
+        let result = resolve_workspace_path("missing", &directories);
+        assert!(result.unwrap_err().contains("文件夹不存在"));
+
+        let result = resolve_workspace_path("", &directories);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_search_workspaces_empty_query_prefers_frecent() {
+        // 空查询时最近/最常打开的工作区排在最前，其余按字典序
+        let temp_dir = TempDir::new().unwrap();
+        for name in ["alpha", "beta", "gamma"] {
+            fs::create_dir_all(temp_dir.path().join(name)).unwrap();
+        }
+        let directories = vec![SearchDirectory::new(temp_dir.path().to_string_lossy())];
+
+        let frecency = FrecencyStore::in_memory();
+        let gamma = temp_dir.path().join("gamma").to_string_lossy().to_string();
+        let beta = temp_dir.path().join("beta").to_string_lossy().to_string();
+        frecency.record_open(&gamma).unwrap();
+        frecency.record_open(&gamma).unwrap();
+        frecency.record_open(&beta).unwrap();
+
+        let results = search_index(
+            &WorkspaceIndex::new(),
+            &frecency,
+            "",
+            &directories,
+            &SearchOptions::default(),
+        )
+        .unwrap()
+        .hits;
+        assert_eq!(hit_names(&results), vec!["gamma", "beta", "alpha"]);
+    }
+
+    #[test]
+    fn test_search_workspaces_frecency_boosts_matches() {
+        // 同等匹配质量下，经常打开的工作区排在前面
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("api-client")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("api-server")).unwrap();
+        let directories = vec![SearchDirectory::new(temp_dir.path().to_string_lossy())];
+
+        let frecency = FrecencyStore::in_memory();
+        let server = temp_dir
+            .path()
+            .join("api-server")
+            .to_string_lossy()
+            .to_string();
+        frecency.record_open(&server).unwrap();
+
+        let results = search_index(
+            &WorkspaceIndex::new(),
+            &frecency,
+            "api",
+            &directories,
+            &SearchOptions::default(),
+        )
+        .unwrap()
+        .hits;
+        assert_eq!(hit_names(&results), vec!["api-server", "api-client"]);
+        assert!(results[0].score > results[1].score);
+    }
+
+    #[test]
+    fn test_search_workspaces_ranks_best_match_first() {
+        // 所有匹配方式统一评分：完全匹配 > 前缀 > 词首 > 中间包含 > 拼音
+        let temp_dir = TempDir::new().unwrap();
+        for name in ["rapid", "web-api", "api-server", "api", "爱派"] {
+            fs::create_dir_all(temp_dir.path().join(name)).unwrap();
+        }
+
+        let results = search_workspaces(
+            "api".to_string(),
+            vec![temp_dir.path().to_string_lossy().to_string()],
+        )
+        .unwrap();
+        assert_eq!(
+            hit_names(&results)[..4],
+            ["api", "api-server", "web-api", "rapid"]
+        );
+    }
+
+    #[test]
+    fn test_search_workspaces_respects_result_limit() {
+        let temp_dir = TempDir::new().unwrap();
+        for i in 0..5 {
+            fs::create_dir_all(temp_dir.path().join(format!("project-{}", i))).unwrap();
+        }
+        let directories = vec![SearchDirectory::new(temp_dir.path().to_string_lossy())];
+        let search = |limit: Option<usize>| {
+            search_index(
+                &WorkspaceIndex::new(),
+                &FrecencyStore::in_memory(),
+                "project",
+                &directories,
+                &SearchOptions {
+                    limit,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .hits
+            .len()
+        };
+
+        assert_eq!(search(Some(2)), 2);
+        // 0 表示不限制
+        assert_eq!(search(Some(0)), 5);
+        assert_eq!(search(None), 5);
+    }
+
+    #[test]
+    fn test_search_workspaces_subsequence_match() {
+        // 子序列匹配：跳过中间字符，优先落在词首且连续的对齐
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_dir = temp_dir.path();
+        fs::create_dir_all(workspace_dir.join("yesterday-stories-rc")).unwrap();
+        fs::create_dir_all(workspace_dir.join("ytools-src-tauri")).unwrap();
+        fs::create_dir_all(workspace_dir.join("other")).unwrap();
+
+        let results = search_workspaces(
+            "ytsrc".to_string(),
+            vec![workspace_dir.to_string_lossy().to_string()],
+        )
+        .unwrap();
+        assert_eq!(
+            hit_names(&results),
+            vec!["ytools-src-tauri", "yesterday-stories-rc"]
+        );
+        assert_eq!(results[0].match_kind, Some(MatchKind::Subsequence));
+    }
+
+    #[test]
+    fn test_search_workspaces_subsequence_camel_case() {
+        // 驼峰边界加分：fb 优先匹配 FooBar
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_dir = temp_dir.path();
+        fs::create_dir_all(workspace_dir.join("flatbuffers")).unwrap();
+        fs::create_dir_all(workspace_dir.join("FooBar")).unwrap();
+
+        let results = search_workspaces(
+            "fbr".to_string(),
+            vec![workspace_dir.to_string_lossy().to_string()],
+        )
+        .unwrap();
+        assert_eq!(results[0].name, "FooBar");
+        assert_eq!(results[0].match_kind, Some(MatchKind::Subsequence));
+    }
+
+    #[test]
+    fn test_search_workspaces_mixed_pinyin() {
+        // 全拼与首字母混合输入
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_dir = temp_dir.path();
+        fs::create_dir_all(workspace_dir.join("项目管理")).unwrap();
+        fs::create_dir_all(workspace_dir.join("测试文档")).unwrap();
+
+        for query in ["xiangmgl", "xmguanli", "xiangmuguanli"] {
+            let results = search_workspaces(
+                query.to_string(),
+                vec![workspace_dir.to_string_lossy().to_string()],
+            )
+            .unwrap();
+            assert_eq!(hit_names(&results), vec!["项目管理"], "query: {}", query);
+        }
+    }
+
+    #[test]
+    fn test_search_workspaces_fuzzy_pinyin_rules() {
+        // 模糊拼音需要按规则开启：zongxin 匹配“中心”依赖 zh/z
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("中心")).unwrap();
+        let directories = vec![SearchDirectory::new(temp_dir.path().to_string_lossy())];
+        let search = |options: &SearchOptions| {
+            search_index(
+                &WorkspaceIndex::new(),
+                &FrecencyStore::in_memory(),
+                "zongxin",
+                &directories,
+                options,
+            )
+            .unwrap()
+            .hits
+        };
+
+        let results = search(&SearchOptions::default());
+        assert!(results
+            .iter()
+            .all(|hit| hit.match_kind != Some(MatchKind::PinyinFull)));
+
+        let options = SearchOptions {
+            fuzzy_pinyin: FuzzyPinyinRules {
+                zh_z: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let results = search(&options);
+        assert_eq!(hit_names(&results), vec!["中心"]);
+        assert_eq!(results[0].match_kind, Some(MatchKind::PinyinFull));
+        assert_eq!(results[0].highlights, vec![MatchRange { start: 0, end: 2 }]);
+    }
+
+    #[test]
+    fn test_search_workspaces_traditional_and_kana() {
+        // 繁体字名称按简体拼音匹配，假名名称按罗马字匹配
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_dir = temp_dir.path();
+        fs::create_dir_all(workspace_dir.join("項目管理")).unwrap();
+        fs::create_dir_all(workspace_dir.join("さくら")).unwrap();
+        fs::create_dir_all(workspace_dir.join("トウキョウ")).unwrap();
+
+        let search = |query: &str| {
+            search_workspaces(
+                query.to_string(),
+                vec![workspace_dir.to_string_lossy().to_string()],
+            )
+            .unwrap()
+        };
+
+        let results = search("xmgl");
+        assert_eq!(hit_names(&results), vec!["項目管理"]);
+        assert_eq!(results[0].match_kind, Some(MatchKind::PinyinInitials));
+
+        let results = search("sakura");
+        assert_eq!(hit_names(&results), vec!["さくら"]);
+        assert_eq!(results[0].match_kind, Some(MatchKind::PinyinFull));
+        assert_eq!(results[0].highlights, vec![MatchRange { start: 0, end: 3 }]);
+
+        assert_eq!(hit_names(&search("toukyou")), vec!["トウキョウ"]);
+
+        // 繁简一对多的字保留自身读音
+        fs::create_dir_all(workspace_dir.join("乾隆")).unwrap();
+        assert_eq!(hit_names(&search("qlong")), vec!["乾隆"]);
+    }
+
+    #[test]
+    fn test_search_workspaces_accented_and_full_width() {
+        // 带重音与全角字符的名称可以用普通 ASCII 查询命中
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_dir = temp_dir.path();
+        fs::create_dir_all(workspace_dir.join("Café-App")).unwrap();
+        fs::create_dir_all(workspace_dir.join("ＡＢＣ工具")).unwrap();
+
+        let search = |query: &str| {
+            search_workspaces(
+                query.to_string(),
+                vec![workspace_dir.to_string_lossy().to_string()],
+            )
+            .unwrap()
+        };
+
+        let results = search("cafeapp");
+        assert_eq!(hit_names(&results), vec!["Café-App"]);
+        assert_eq!(results[0].match_kind, Some(MatchKind::Normalized));
+        assert_eq!(
+            results[0].highlights,
+            vec![
+                MatchRange { start: 0, end: 4 },
+                MatchRange { start: 5, end: 8 }
+            ]
+        );
+
+        let results = search("abc");
+        assert_eq!(hit_names(&results), vec!["ＡＢＣ工具"]);
+        assert_eq!(results[0].match_kind, Some(MatchKind::Normalized));
+    }
+
+    #[test]
+    fn test_search_workspaces_query_operators() {
+        // @别名 限定搜索目录，-词 排除，^/$ 锚点与引号短语过滤名称
+        let temp_dir = TempDir::new().unwrap();
+        let work = temp_dir.path().join("work");
+        let personal = temp_dir.path().join("personal");
+        for name in ["api-gateway", "api-legacy", "billing-api", "user service"] {
+            fs::create_dir_all(work.join(name)).unwrap();
+        }
+        fs::create_dir_all(personal.join("api-playground")).unwrap();
+        let directories = vec![
+            SearchDirectory {
+                alias: Some("clients".to_string()),
+                ..SearchDirectory::new(work.to_string_lossy())
+            },
+            SearchDirectory::new(personal.to_string_lossy()),
+        ];
+        let search = |query: &str| {
+            let mut names = hit_names(
+                &search_index(
+                    &WorkspaceIndex::new(),
+                    &FrecencyStore::in_memory(),
+                    query,
+                    &directories,
+                    &SearchOptions::default(),
+                )
+                .unwrap()
+                .hits,
+            );
+            names.sort();
+            names
+        };
+
+        assert_eq!(
+            search("api"),
+            vec!["api-gateway", "api-legacy", "api-playground", "billing-api"]
+        );
+        assert_eq!(search("@personal api"), vec!["api-playground"]);
+        assert_eq!(
+            search("@clients api -legacy"),
+            vec!["api-gateway", "billing-api"]
+        );
+        assert_eq!(
+            search("^api"),
+            vec!["api-gateway", "api-legacy", "api-playground"]
+        );
+        assert_eq!(search("api$"), vec!["billing-api"]);
+        assert_eq!(search("\"user service\""), vec!["user service"]);
+        assert!(search("\"user-service\"").is_empty());
+        assert!(search("@unknown api").is_empty());
+    }
+
+    #[test]
+    fn test_search_workspaces_operator_highlights() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("api-gateway")).unwrap();
+        let results = search_workspaces(
+            "^api way".to_string(),
+            vec![temp_dir.path().to_string_lossy().to_string()],
+        )
+        .unwrap();
+        assert_eq!(results[0].match_kind, Some(MatchKind::Literal));
+        assert_eq!(
+            results[0].highlights,
+            vec![
+                MatchRange { start: 0, end: 3 },
+                MatchRange { start: 8, end: 11 }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_search_workspaces_reports_root_status() {
+        // 不存在的搜索目录不影响其他目录的结果，并在状态中标记出来
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("api")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("web")).unwrap();
+        let root = temp_dir.path().to_string_lossy().to_string();
+        let directories = vec![
+            SearchDirectory::new("/nonexistent/path"),
+            SearchDirectory::new(&root),
+        ];
+
+        let results = search_index(
+            &WorkspaceIndex::new(),
+            &FrecencyStore::in_memory(),
+            "api",
+            &directories,
+            &SearchOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(hit_names(&results.hits), vec!["api"]);
+        assert_eq!(
+            results.roots,
+            vec![
+                RootStatus {
+                    root: "/nonexistent/path".to_string(),
+                    state: RootState::Missing,
+                    entries: 0,
+                },
+                RootStatus {
+                    root,
+                    state: RootState::Ok,
+                    entries: 2,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_search_workspaces_project_info_and_type_filter() {
+        // 结果携带项目类型与语言，并可按类型筛选
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("app-server")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("app-web")).unwrap();
+        fs::write(temp_dir.path().join("app-server/go.mod"), "").unwrap();
+        fs::write(temp_dir.path().join("app-web/package.json"), "").unwrap();
+        let directories = vec![SearchDirectory::new(temp_dir.path().to_string_lossy())];
+        let search = |project_types: Vec<ProjectType>| {
+            search_index(
+                &WorkspaceIndex::new(),
+                &FrecencyStore::in_memory(),
+                "app",
+                &directories,
+                &SearchOptions {
+                    project_types,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .hits
+        };
+
+        let results = search(Vec::new());
+        assert_eq!(hit_names(&results), vec!["app-web", "app-server"]);
+        assert_eq!(results[0].project.build_tools, vec!["npm"]);
+        assert_eq!(results[1].project.project_types, vec![ProjectType::Go]);
+        assert_eq!(results[1].project.language, Some("Go"));
+
+        let results = search(vec![ProjectType::Node]);
+        assert_eq!(hit_names(&results), vec!["app-web"]);
+    }
+}
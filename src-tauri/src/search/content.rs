@@ -0,0 +1,600 @@
+//! 文件内容搜索
+//!
+//! 在搜索目录下遍历文件（与文件搜索相同，遵循排除模式与忽略文件），逐行查找字面量或正则表达式。
+//! 搜索在后台线程中进行，每个命中的文件通过 `content-search-hit` 事件推送给前端，
+//! 结束（或被取消）时推送 `content-search-done`。新的搜索开始时会取消上一次尚未完成的搜索（见 `tasks` 模块）。
+
+use super::discovery::into_directories;
+use super::files::{walk_files, DEFAULT_FILE_MAX_DEPTH, DEFAULT_MAX_FILES, FILE_MAX_DEPTH_LIMIT};
+use super::roots::{probe_root, with_timeout, RootState, RootStatus, DEFAULT_ROOT_TIMEOUT_MS};
+use super::tasks::{CancelToken, SearchTasks, TaskKind, POLL_INTERVAL};
+use super::{MatchRange, SearchDirectory, SearchDirectoryInput};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+
+/// 命中文件事件
+pub const CONTENT_HIT_EVENT: &str = "content-search-hit";
+/// 搜索结束事件
+pub const CONTENT_DONE_EVENT: &str = "content-search-done";
+
+/// 默认跳过超过该大小的文件（1 MiB）
+pub const DEFAULT_MAX_FILE_SIZE: u64 = 1024 * 1024;
+/// 默认最多返回的命中行数
+pub const DEFAULT_MAX_MATCHES: usize = 1000;
+/// 单个文件最多返回的命中行数
+const MAX_MATCHES_PER_FILE: usize = 50;
+/// 检查文件开头多少字节来判断是否为二进制文件
+const BINARY_SNIFF_LEN: usize = 8192;
+/// 命中行预览的最大字符数
+const MAX_PREVIEW_CHARS: usize = 240;
+/// 预览截断时保留在第一个命中位置之前的字符数
+const PREVIEW_CONTEXT_CHARS: usize = 40;
+
+/// 大小写匹配方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CaseMode {
+    /// 查询全为小写时忽略大小写，否则区分大小写
+    #[default]
+    Smart,
+    Sensitive,
+    Insensitive,
+}
+
+/// 内容搜索选项
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ContentSearchOptions {
+    /// 按正则表达式匹配，默认按字面量匹配
+    pub regex: bool,
+    /// 大小写匹配方式
+    pub case_mode: CaseMode,
+    /// 跳过超过该大小（字节）的文件，为空时使用默认值
+    pub max_file_size: Option<u64>,
+    /// 最多返回的命中行数，为空时使用默认值
+    pub max_matches: Option<usize>,
+    /// 文件遍历深度，为空时使用默认值
+    pub max_depth: Option<usize>,
+    /// 所有搜索目录合计最多遍历的文件数，为空时使用默认值
+    pub max_files: Option<usize>,
+    /// 单个搜索目录无进展的超时（毫秒），为空时使用默认值
+    pub root_timeout_ms: Option<u64>,
+}
+
+/// 命中的一行
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentLine {
+    /// 行号（从 1 开始）
+    pub line: usize,
+    /// 行内容预览（过长时截断）
+    pub text: String,
+    /// 预览中命中的字符区间
+    pub highlights: Vec<MatchRange>,
+}
+
+/// 命中文件事件的负载
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentHit {
+    pub request_id: u64,
+    /// 文件的绝对路径
+    pub path: String,
+    /// 所属的搜索目录
+    pub root: String,
+    pub lines: Vec<ContentLine>,
+}
+
+/// 搜索结束事件的负载
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentSearchDone {
+    pub request_id: u64,
+    /// 实际读取的文件数
+    pub scanned_files: usize,
+    /// 命中的文件数
+    pub matched_files: usize,
+    /// 命中的行数
+    pub matched_lines: usize,
+    /// 是否因达到命中行数上限而提前结束
+    pub truncated: bool,
+    /// 是否被取消
+    pub cancelled: bool,
+    /// 已搜索的各目录状态，条目数为遍历到的文件数
+    pub roots: Vec<RootStatus>,
+}
+
+/// 在搜索目录下搜索文件内容
+///
+/// 立即返回；结果通过 `content-search-hit` / `content-search-done` 事件推送，事件中带有 `request_id`，
+/// 前端据此丢弃过期的结果。查询为空或正则表达式无效时返回错误。
+#[tauri::command]
+pub fn search_content(
+    app: tauri::AppHandle,
+    tasks: tauri::State<'_, SearchTasks>,
+    request_id: u64,
+    query: String,
+    directories: Vec<SearchDirectoryInput>,
+    options: Option<ContentSearchOptions>,
+) -> Result<(), String> {
+    let options = options.unwrap_or_default();
+    let matcher = build_matcher(&query, &options)?;
+    let directories = into_directories(directories);
+    let cancel = tasks.start(TaskKind::Content, request_id);
+    let tasks = SearchTasks::clone(&tasks);
+
+    std::thread::spawn(move || {
+        let done = run_content_search(
+            request_id,
+            &matcher,
+            &directories,
+            &options,
+            &cancel,
+            |hit| {
+                let _ = app.emit(CONTENT_HIT_EVENT, hit);
+            },
+        );
+        let _ = app.emit(CONTENT_DONE_EVENT, done);
+        tasks.finish(TaskKind::Content, request_id);
+    });
+    Ok(())
+}
+
+/// 按选项编译查询
+pub(crate) fn build_matcher(query: &str, options: &ContentSearchOptions) -> Result<Regex, String> {
+    if query.is_empty() {
+        return Err("搜索内容不能为空".to_string());
+    }
+    let pattern = if options.regex {
+        query.to_string()
+    } else {
+        regex::escape(query)
+    };
+    let case_insensitive = match options.case_mode {
+        CaseMode::Sensitive => false,
+        CaseMode::Insensitive => true,
+        CaseMode::Smart => !query.chars().any(char::is_uppercase),
+    };
+    RegexBuilder::new(&pattern)
+        .case_insensitive(case_insensitive)
+        .build()
+        .map_err(|e| format!("无效的正则表达式: {}", e))
+}
+
+/// 执行内容搜索，每个命中的文件调用一次 `on_hit`，返回统计信息
+///
+/// 各搜索目录依次在独立线程中遍历，共用同一个文件数上限；某个目录超过 `root_timeout_ms`
+/// 没有任何进展时视为超时并跳过。取消在遍历每个文件前检查。
+pub(crate) fn run_content_search(
+    request_id: u64,
+    matcher: &Regex,
+    directories: &[SearchDirectory],
+    options: &ContentSearchOptions,
+    cancel: &CancelToken,
+    mut on_hit: impl FnMut(ContentHit),
+) -> ContentSearchDone {
+    let max_depth = options
+        .max_depth
+        .unwrap_or(DEFAULT_FILE_MAX_DEPTH)
+        .clamp(1, FILE_MAX_DEPTH_LIMIT);
+    let max_file_size = options.max_file_size.unwrap_or(DEFAULT_MAX_FILE_SIZE);
+    let max_matches = options.max_matches.unwrap_or(DEFAULT_MAX_MATCHES);
+    let timeout = Duration::from_millis(options.root_timeout_ms.unwrap_or(DEFAULT_ROOT_TIMEOUT_MS));
+    let mut remaining_files = options.max_files.unwrap_or(DEFAULT_MAX_FILES);
+
+    let mut done = ContentSearchDone {
+        request_id,
+        ..Default::default()
+    };
+    for dir in directories {
+        if cancel.is_cancelled() {
+            done.cancelled = true;
+            break;
+        }
+        if done.truncated || remaining_files == 0 {
+            break;
+        }
+
+        let root = PathBuf::from(&dir.path);
+        let mut visited = 0;
+        let result = with_timeout(timeout, cancel, move || probe_root(&root)).and_then(|()| {
+            let search = RootSearch {
+                request_id,
+                matcher: matcher.clone(),
+                directory: dir.clone(),
+                max_depth,
+                max_file_size,
+                max_files: remaining_files,
+                max_matches: max_matches - done.matched_lines,
+            };
+            let receiver = search.spawn(cancel.clone());
+            let mut last_progress = Instant::now();
+            loop {
+                if cancel.is_cancelled() {
+                    return Ok(());
+                }
+                match receiver.recv_timeout(POLL_INTERVAL) {
+                    Ok(RootEvent::File { scanned, hit }) => {
+                        last_progress = Instant::now();
+                        visited += 1;
+                        done.scanned_files += usize::from(scanned);
+                        if let Some(hit) = hit {
+                            done.matched_files += 1;
+                            done.matched_lines += hit.lines.len();
+                            on_hit(hit);
+                        }
+                    }
+                    Ok(RootEvent::Truncated) => done.truncated = true,
+                    Err(RecvTimeoutError::Timeout) if last_progress.elapsed() >= timeout => {
+                        // 丢弃接收端后，遍历线程下一次发送失败即退出
+                        return Err(RootState::TimedOut);
+                    }
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => return Ok(()),
+                }
+            }
+        });
+        remaining_files = remaining_files.saturating_sub(visited);
+        done.roots
+            .push(RootStatus::from_result(&dir.path, &result, |_| visited));
+    }
+    if cancel.is_cancelled() {
+        done.cancelled = true;
+    }
+    done
+}
+
+/// 遍历线程发回的事件
+enum RootEvent {
+    /// 遍历到一个文件；`scanned` 为 false 表示文件过大、无法读取或为二进制而被跳过
+    File {
+        scanned: bool,
+        hit: Option<ContentHit>,
+    },
+    /// 命中行数已达上限，目录下还有未搜索的文件
+    Truncated,
+}
+
+/// 单个搜索目录的内容搜索，在独立线程中执行
+struct RootSearch {
+    request_id: u64,
+    matcher: Regex,
+    directory: SearchDirectory,
+    max_depth: usize,
+    max_file_size: u64,
+    /// 本目录最多遍历的文件数（所有目录共用的上限中剩余的部分）
+    max_files: usize,
+    /// 本目录最多返回的命中行数
+    max_matches: usize,
+}
+
+impl RootSearch {
+    fn spawn(self, cancel: CancelToken) -> Receiver<RootEvent> {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || self.run(&cancel, &sender));
+        receiver
+    }
+
+    fn run(&self, cancel: &CancelToken, sender: &Sender<RootEvent>) {
+        let mut files = 0;
+        let mut matches = 0;
+        walk_files(&self.directory, self.max_depth, |path| {
+            if cancel.is_cancelled() || files >= self.max_files {
+                return ControlFlow::Break(());
+            }
+            if matches >= self.max_matches {
+                let _ = sender.send(RootEvent::Truncated);
+                return ControlFlow::Break(());
+            }
+            files += 1;
+
+            let event = match read_text_file(&path, self.max_file_size) {
+                Some(content) => {
+                    let limit = (self.max_matches - matches).min(MAX_MATCHES_PER_FILE);
+                    let lines = search_lines(&self.matcher, &content, limit);
+                    matches += lines.len();
+                    let hit = (!lines.is_empty()).then(|| ContentHit {
+                        request_id: self.request_id,
+                        path: path.to_string_lossy().to_string(),
+                        root: self.directory.path.clone(),
+                        lines,
+                    });
+                    RootEvent::File { scanned: true, hit }
+                }
+                None => RootEvent::File {
+                    scanned: false,
+                    hit: None,
+                },
+            };
+            // 发送失败说明搜索已放弃本目录（超时或取消）
+            match sender.send(event) {
+                Ok(()) => ControlFlow::Continue(()),
+                Err(_) => ControlFlow::Break(()),
+            }
+        });
+    }
+}
+
+/// 读取文本文件，超过大小限制、无法读取或判定为二进制时返回 None
+fn read_text_file(path: &Path, max_size: u64) -> Option<String> {
+    let metadata = fs::metadata(path).ok()?;
+    if metadata.len() > max_size {
+        return None;
+    }
+    let bytes = fs::read(path).ok()?;
+    // 与 git/ripgrep 相同：开头出现 NUL 字节即视为二进制文件
+    if bytes.iter().take(BINARY_SNIFF_LEN).any(|&b| b == 0) {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// 逐行查找，最多返回 `limit` 行
+fn search_lines(matcher: &Regex, content: &str, limit: usize) -> Vec<ContentLine> {
+    let mut lines = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        if lines.len() >= limit {
+            break;
+        }
+        let ranges: Vec<(usize, usize)> = matcher
+            .find_iter(line)
+            .filter(|m| !m.is_empty())
+            .map(|m| (m.start(), m.end()))
+            .collect();
+        if ranges.is_empty() {
+            continue;
+        }
+        lines.push(preview(index + 1, line, &ranges));
+    }
+    lines
+}
+
+/// 生成命中行的预览：字节区间转换为字符区间，过长的行围绕第一个命中位置截断
+fn preview(line_number: usize, line: &str, ranges: &[(usize, usize)]) -> ContentLine {
+    let char_index = |byte: usize| line[..byte].chars().count();
+    let total = line.chars().count();
+    let first = char_index(ranges[0].0);
+    let start = if total > MAX_PREVIEW_CHARS {
+        first
+            .saturating_sub(PREVIEW_CONTEXT_CHARS)
+            .min(total - MAX_PREVIEW_CHARS)
+    } else {
+        0
+    };
+    let end = (start + MAX_PREVIEW_CHARS).min(total);
+
+    let highlights = ranges
+        .iter()
+        .map(|&(s, e)| (char_index(s), char_index(e)))
+        .filter(|&(s, e)| s < end && e > start)
+        .map(|(s, e)| MatchRange {
+            start: s.max(start) - start,
+            end: e.min(end) - start,
+        })
+        .collect();
+
+    ContentLine {
+        line: line_number,
+        text: line.chars().skip(start).take(end - start).collect(),
+        highlights,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn run(
+        query: &str,
+        root: &Path,
+        options: &ContentSearchOptions,
+    ) -> (Vec<ContentHit>, ContentSearchDone) {
+        let matcher = build_matcher(query, options).unwrap();
+        let directories = vec![SearchDirectory::new(root.to_string_lossy())];
+        let mut hits = Vec::new();
+        let done = run_content_search(
+            7,
+            &matcher,
+            &directories,
+            options,
+            &CancelToken::default(),
+            |hit| hits.push(hit),
+        );
+        hits.sort_by(|a, b| a.path.cmp(&b.path));
+        (hits, done)
+    }
+
+    fn sample_tree() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("api/config")).unwrap();
+        fs::create_dir_all(root.join("web/node_modules")).unwrap();
+        fs::write(
+            root.join("api/config/app.yml"),
+            "name: api\nDATABASE_URL: postgres://db\nport: 8080\n",
+        )
+        .unwrap();
+        fs::write(root.join("web/.env"), "database_url=sqlite\n").unwrap();
+        fs::write(root.join("web/notes.txt"), "数据库地址 database_url\n").unwrap();
+        fs::write(root.join("web/node_modules/dep.js"), "database_url\n").unwrap();
+        fs::write(root.join("web/image.bin"), b"database_url\0\x01\x02").unwrap();
+        temp_dir
+    }
+
+    #[test]
+    fn test_content_search_literal_smart_case() {
+        let temp_dir = sample_tree();
+        let (hits, done) = run(
+            "database_url",
+            temp_dir.path(),
+            &ContentSearchOptions::default(),
+        );
+        // 隐藏文件、node_modules 与二进制文件都被跳过；全小写查询忽略大小写
+        assert_eq!(hits.len(), 2);
+        assert!(hits[0].path.ends_with("app.yml"));
+        assert_eq!(hits[0].request_id, 7);
+        assert_eq!(hits[0].lines[0].line, 2);
+        assert_eq!(
+            hits[0].lines[0].highlights,
+            vec![MatchRange { start: 0, end: 12 }]
+        );
+        // 高亮按字符计数
+        assert_eq!(
+            hits[1].lines[0].highlights,
+            vec![MatchRange { start: 6, end: 18 }]
+        );
+        assert_eq!(done.matched_files, 2);
+        assert!(!done.cancelled && !done.truncated);
+
+        // 含大写字母时区分大小写
+        let (hits, _) = run(
+            "DATABASE_URL",
+            temp_dir.path(),
+            &ContentSearchOptions::default(),
+        );
+        assert_eq!(hits.len(), 1);
+    }
+
+    #[test]
+    fn test_content_search_regex_and_case_options() {
+        let temp_dir = sample_tree();
+        let options = ContentSearchOptions {
+            regex: true,
+            case_mode: CaseMode::Sensitive,
+            ..Default::default()
+        };
+        let (hits, _) = run(r"port: \d+", temp_dir.path(), &options);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].lines[0].text, "port: 8080");
+
+        // 字面量模式下正则元字符按原样匹配
+        let (hits, _) = run(r"\d+", temp_dir.path(), &ContentSearchOptions::default());
+        assert!(hits.is_empty());
+
+        let err = build_matcher("(", &options).unwrap_err();
+        assert!(err.contains("无效的正则表达式"));
+        assert!(build_matcher("", &options).is_err());
+    }
+
+    #[test]
+    fn test_content_search_limits() {
+        let temp_dir = sample_tree();
+        let options = ContentSearchOptions {
+            max_file_size: Some(30),
+            ..Default::default()
+        };
+        let (hits, _) = run("database_url", temp_dir.path(), &options);
+        assert_eq!(hits.len(), 1);
+        assert!(hits[0].path.ends_with("notes.txt"));
+
+        let options = ContentSearchOptions {
+            max_matches: Some(1),
+            ..Default::default()
+        };
+        let (hits, done) = run("database_url", temp_dir.path(), &options);
+        assert_eq!(hits.len(), 1);
+        assert!(done.truncated);
+    }
+
+    #[test]
+    fn test_content_search_cancellation() {
+        let temp_dir = sample_tree();
+        let options = ContentSearchOptions::default();
+        let matcher = build_matcher("database", &options).unwrap();
+        let directories = vec![SearchDirectory::new(temp_dir.path().to_string_lossy())];
+        let cancel = CancelToken::default();
+        cancel.cancel();
+        let mut hits = 0;
+        let done = run_content_search(1, &matcher, &directories, &options, &cancel, |_| hits += 1);
+        assert!(done.cancelled);
+        assert_eq!(hits, 0);
+    }
+
+    #[test]
+    fn test_content_search_shares_file_budget_across_roots() {
+        let first = sample_tree();
+        let second = sample_tree();
+        let options = ContentSearchOptions {
+            max_files: Some(3),
+            ..Default::default()
+        };
+        let matcher = build_matcher("database_url", &options).unwrap();
+        let directories = vec![
+            SearchDirectory::new(first.path().to_string_lossy()),
+            SearchDirectory::new("/nonexistent/path"),
+            SearchDirectory::new(second.path().to_string_lossy()),
+        ];
+        let done = run_content_search(
+            1,
+            &matcher,
+            &directories,
+            &options,
+            &CancelToken::default(),
+            |_| {},
+        );
+        // 第一个目录用完 3 个文件的上限，后面的目录不再搜索
+        assert_eq!(done.roots.len(), 1);
+        assert_eq!(done.roots[0].state, RootState::Ok);
+        assert_eq!(done.roots[0].entries, 3);
+
+        let options = ContentSearchOptions {
+            max_files: Some(5),
+            ..Default::default()
+        };
+        let done = run_content_search(
+            1,
+            &matcher,
+            &directories,
+            &options,
+            &CancelToken::default(),
+            |_| {},
+        );
+        let states: Vec<_> = done.roots.iter().map(|r| (r.state, r.entries)).collect();
+        assert_eq!(
+            states,
+            vec![
+                (RootState::Ok, 3),
+                (RootState::Missing, 0),
+                (RootState::Ok, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_content_search_cancel_during_walk() {
+        let temp_dir = sample_tree();
+        let options = ContentSearchOptions::default();
+        let matcher = build_matcher("database_url", &options).unwrap();
+        let directories = vec![SearchDirectory::new(temp_dir.path().to_string_lossy())];
+        let cancel = CancelToken::default();
+        let mut hits = 0;
+        // 收到第一个命中后取消，不再推送后续命中
+        let done = run_content_search(1, &matcher, &directories, &options, &cancel, |_| {
+            hits += 1;
+            cancel.cancel();
+        });
+        assert!(done.cancelled);
+        assert_eq!(hits, 1);
+    }
+
+    #[test]
+    fn test_preview_truncates_long_lines() {
+        let line = format!("{}needle{}", "a".repeat(300), "b".repeat(300));
+        let preview = preview(1, &line, &[(300, 306)]);
+        assert_eq!(preview.text.chars().count(), MAX_PREVIEW_CHARS);
+        assert_eq!(
+            preview.highlights,
+            vec![MatchRange {
+                start: PREVIEW_CONTEXT_CHARS,
+                end: PREVIEW_CONTEXT_CHARS + 6
+            }]
+        );
+    }
+}
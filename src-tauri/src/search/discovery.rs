@@ -0,0 +1,599 @@
+//! 工作区发现
+//!
+//! 按搜索目录的配置向下遍历：包含项目标记文件（如 `.git`、`Cargo.toml`）的目录视为工作区，
+//! 不再继续深入；到达最大深度的目录同样作为工作区返回；其余中间目录（如 `~/code/<org>`）只用于继续向下查找。
+//! 遍历时按排除模式、隐藏目录开关以及沿途的 `.gitignore`/`.ignore` 文件跳过目录，
+//! 包含模式只用于筛选最终返回的工作区。
+
+use super::roots::{probe_root, RootState};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 默认的最大遍历深度（1 表示只列出搜索目录的直接子文件夹）
+pub const DEFAULT_MAX_DEPTH: usize = 1;
+
+/// 允许配置的最大遍历深度上限，避免误配置导致遍历整个磁盘
+pub const MAX_DEPTH_LIMIT: usize = 8;
+
+/// 默认的项目标记：目录中存在任意一项即视为工作区，`*.ext` 形式按扩展名匹配
+pub const DEFAULT_PROJECT_MARKERS: &[&str] = &[
+    ".git",
+    ".hg",
+    ".svn",
+    "Cargo.toml",
+    "package.json",
+    "pom.xml",
+    "build.gradle",
+    "build.gradle.kts",
+    "go.mod",
+    "pyproject.toml",
+    "setup.py",
+    "requirements.txt",
+    "composer.json",
+    "Gemfile",
+    "CMakeLists.txt",
+    "*.sln",
+    "*.csproj",
+];
+
+/// 默认的排除模式：依赖、构建产物和缓存目录
+pub const DEFAULT_EXCLUDE_PATTERNS: &[&str] = &["node_modules", ".git", "target", "__pycache__"];
+
+/// 搜索目录及其遍历选项
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchDirectory {
+    /// 搜索目录路径
+    pub path: String,
+    /// 最大遍历深度
+    #[serde(default = "default_max_depth")]
+    pub max_depth: usize,
+    /// 项目标记文件/目录名，为空时使用默认列表
+    #[serde(default)]
+    pub project_markers: Vec<String>,
+    /// 别名，查询中可用 `@别名` 只搜索该目录，未设置时使用目录名
+    #[serde(default)]
+    pub alias: Option<String>,
+    /// 包含模式（glob），非空时只返回名称或相对路径匹配任一模式的工作区
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// 排除模式（glob），名称或相对路径匹配任一模式的目录不会被遍历，未配置时使用默认列表
+    #[serde(default = "default_exclude")]
+    pub exclude: Vec<String>,
+    /// 是否跳过隐藏目录（以 `.` 开头）
+    #[serde(default = "default_skip_hidden")]
+    pub skip_hidden: bool,
+}
+
+fn default_max_depth() -> usize {
+    DEFAULT_MAX_DEPTH
+}
+
+fn default_exclude() -> Vec<String> {
+    DEFAULT_EXCLUDE_PATTERNS
+        .iter()
+        .map(|pattern| pattern.to_string())
+        .collect()
+}
+
+fn default_skip_hidden() -> bool {
+    true
+}
+
+impl SearchDirectory {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            max_depth: DEFAULT_MAX_DEPTH,
+            project_markers: Vec::new(),
+            alias: None,
+            include: Vec::new(),
+            exclude: default_exclude(),
+            skip_hidden: default_skip_hidden(),
+        }
+    }
+
+    /// 判断查询中的 `@别名` 是否指向该目录（忽略大小写）
+    pub fn matches_alias(&self, alias: &str) -> bool {
+        let name = match &self.alias {
+            Some(alias) => alias.as_str(),
+            None => Path::new(&self.path)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or(&self.path),
+        };
+        name.to_lowercase() == alias.to_lowercase()
+    }
+
+    /// 实际使用的遍历深度（限制在 1..=MAX_DEPTH_LIMIT）
+    pub fn effective_max_depth(&self) -> usize {
+        self.max_depth.clamp(1, MAX_DEPTH_LIMIT)
+    }
+
+    /// 判断目录是否包含项目标记
+    fn is_project(&self, dir: &Path) -> bool {
+        if self.project_markers.is_empty() {
+            has_project_marker(dir, DEFAULT_PROJECT_MARKERS.iter().copied())
+        } else {
+            has_project_marker(dir, self.project_markers.iter().map(String::as_str))
+        }
+    }
+}
+
+impl From<&str> for SearchDirectory {
+    fn from(path: &str) -> Self {
+        Self::new(path)
+    }
+}
+
+impl From<String> for SearchDirectory {
+    fn from(path: String) -> Self {
+        Self::new(path)
+    }
+}
+
+/// 前端传入的搜索目录：兼容旧版的纯路径字符串与带选项的对象
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum SearchDirectoryInput {
+    Path(String),
+    Options(SearchDirectory),
+}
+
+impl From<SearchDirectoryInput> for SearchDirectory {
+    fn from(input: SearchDirectoryInput) -> Self {
+        match input {
+            SearchDirectoryInput::Path(path) => SearchDirectory::new(path),
+            SearchDirectoryInput::Options(directory) => directory,
+        }
+    }
+}
+
+/// 将前端传入的搜索目录列表转换为统一的配置
+pub fn into_directories(inputs: Vec<SearchDirectoryInput>) -> Vec<SearchDirectory> {
+    inputs.into_iter().map(SearchDirectory::from).collect()
+}
+
+/// 发现的工作区
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveredWorkspace {
+    /// 工作区绝对路径
+    pub path: PathBuf,
+    /// 文件夹名
+    pub name: String,
+}
+
+/// 一次遍历的结果
+#[derive(Debug, Default)]
+pub struct Discovery {
+    /// 发现的工作区
+    pub workspaces: Vec<DiscoveredWorkspace>,
+    /// 遍历过的目录（搜索目录本身及中间目录），需要监听其变化
+    pub visited_dirs: Vec<PathBuf>,
+}
+
+/// 按配置遍历搜索目录，目录不存在或不可读时返回对应的状态
+pub fn discover(directory: &SearchDirectory) -> Result<Discovery, RootState> {
+    let root = PathBuf::from(&directory.path);
+    probe_root(&root)?;
+
+    let mut discovery = Discovery::default();
+    let walker = Walker {
+        root: &root,
+        max_depth: directory.effective_max_depth(),
+        directory,
+        filters: Filters::new(directory),
+    };
+    walker.walk(&root, 1, &mut IgnoreStack::default(), &mut discovery);
+    Ok(discovery)
+}
+
+/// 忽略文件名，同一目录下后者优先级更高（与 ripgrep 一致）
+const IGNORE_FILES: &[&str] = &[".gitignore", ".ignore"];
+
+/// 从搜索目录到当前目录沿途的忽略规则，越深的目录优先级越高
+#[derive(Default)]
+pub(crate) struct IgnoreStack {
+    matchers: Vec<Gitignore>,
+}
+
+impl IgnoreStack {
+    /// 进入目录时加载其中的忽略文件，返回是否压入了新的规则（离开目录时需要 `pop`）
+    pub(crate) fn push_dir(&mut self, dir: &Path) -> bool {
+        let mut builder = GitignoreBuilder::new(dir);
+        let mut found = false;
+        for file in IGNORE_FILES {
+            let path = dir.join(file);
+            if path.is_file() {
+                // 单行语法错误不影响其余规则
+                let _ = builder.add(path);
+                found = true;
+            }
+        }
+        if !found {
+            return false;
+        }
+        match builder.build() {
+            Ok(matcher) => {
+                self.matchers.push(matcher);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    pub(crate) fn pop(&mut self) {
+        self.matchers.pop();
+    }
+
+    /// 路径是否被忽略：从最深的规则开始查找，`!pattern` 取反的规则会重新包含该路径
+    pub(crate) fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        for matcher in self.matchers.iter().rev() {
+            match matcher.matched(path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        false
+    }
+}
+
+/// 编译后的包含/排除模式
+pub(crate) struct Filters {
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+    skip_hidden: bool,
+}
+
+impl Filters {
+    pub(crate) fn new(directory: &SearchDirectory) -> Self {
+        let include = if directory.include.is_empty() {
+            None
+        } else {
+            Some(build_glob_set(&directory.include))
+        };
+        Self {
+            include,
+            exclude: build_glob_set(&directory.exclude),
+            skip_hidden: directory.skip_hidden,
+        }
+    }
+
+    /// 目录是否需要跳过（不作为工作区，也不继续向下遍历）
+    pub(crate) fn is_excluded(&self, name: &str, relative: &str) -> bool {
+        (self.skip_hidden && name.starts_with('.'))
+            || self.exclude.is_match(name)
+            || self.exclude.is_match(relative)
+    }
+
+    /// 工作区是否符合包含模式
+    fn is_included(&self, name: &str, relative: &str) -> bool {
+        self.include
+            .as_ref()
+            .is_none_or(|include| include.is_match(name) || include.is_match(relative))
+    }
+}
+
+/// 编译 glob 模式，无效的模式直接忽略
+fn build_glob_set(patterns: &[String]) -> GlobSet {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        if let Ok(glob) = Glob::new(pattern) {
+            builder.add(glob);
+        }
+    }
+    builder.build().unwrap_or_else(|_| GlobSet::empty())
+}
+
+struct Walker<'a> {
+    root: &'a Path,
+    max_depth: usize,
+    directory: &'a SearchDirectory,
+    filters: Filters,
+}
+
+impl Walker<'_> {
+    fn walk(&self, dir: &Path, depth: usize, ignores: &mut IgnoreStack, discovery: &mut Discovery) {
+        discovery.visited_dirs.push(dir.to_path_buf());
+
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        let pushed = ignores.push_dir(dir);
+
+        for entry in entries.flatten() {
+            // 不跟随符号链接，避免循环
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if !metadata.is_dir() {
+                continue;
+            }
+            let Ok(name) = entry.file_name().into_string() else {
+                continue;
+            };
+
+            let path = entry.path();
+            let relative = relative_path(self.root, &path);
+            if self.filters.is_excluded(&name, &relative) || ignores.is_ignored(&path, true) {
+                continue;
+            }
+
+            // 到达最大深度时不再检查标记，直接作为工作区（深度为 1 时与旧行为一致）
+            if depth >= self.max_depth || self.directory.is_project(&path) {
+                if self.filters.is_included(&name, &relative) {
+                    discovery
+                        .workspaces
+                        .push(DiscoveredWorkspace { path, name });
+                }
+            } else {
+                self.walk(&path, depth + 1, ignores, discovery);
+            }
+        }
+
+        if pushed {
+            ignores.pop();
+        }
+    }
+}
+
+/// 相对搜索目录的路径，统一使用 `/` 分隔，便于 glob 匹配
+pub(crate) fn relative_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// 检查目录中是否存在任一标记；`*.ext` 形式的标记按文件扩展名匹配
+fn has_project_marker<'a>(dir: &Path, markers: impl Iterator<Item = &'a str>) -> bool {
+    let mut suffixes: Vec<&str> = Vec::new();
+    for marker in markers {
+        if let Some(suffix) = marker.strip_prefix('*') {
+            suffixes.push(suffix);
+        } else if dir.join(marker).exists() {
+            return true;
+        }
+    }
+
+    if suffixes.is_empty() {
+        return false;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+    entries.flatten().any(|entry| {
+        entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| suffixes.iter().any(|suffix| name.ends_with(suffix)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn discovered_names(directory: &SearchDirectory) -> Vec<String> {
+        let mut names: Vec<String> = discover(directory)
+            .unwrap()
+            .workspaces
+            .into_iter()
+            .map(|w| w.name)
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_discover_default_depth_lists_children() {
+        // 默认深度只列出直接子文件夹，与旧行为一致
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("org/repo/.git")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("plain")).unwrap();
+
+        let directory = SearchDirectory::new(temp_dir.path().to_string_lossy());
+        assert_eq!(discovered_names(&directory), vec!["org", "plain"]);
+    }
+
+    #[test]
+    fn test_discover_nested_projects() {
+        // ~/code/<org>/<repo> 结构：跳过中间的组织目录，返回真正的项目
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("acme/api/.git")).unwrap();
+        fs::create_dir_all(root.join("acme/web")).unwrap();
+        fs::write(root.join("acme/web/package.json"), "{}").unwrap();
+        fs::create_dir_all(root.join("personal/notes")).unwrap();
+
+        let directory = SearchDirectory {
+            max_depth: 2,
+            ..SearchDirectory::new(root.to_string_lossy())
+        };
+        // personal/notes 没有标记，但已到达最大深度，仍作为工作区返回
+        assert_eq!(discovered_names(&directory), vec!["api", "notes", "web"]);
+    }
+
+    #[test]
+    fn test_discover_does_not_descend_into_projects() {
+        // 项目目录内部的子文件夹不会被当作工作区
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("app/packages/core")).unwrap();
+        fs::write(root.join("app/Cargo.toml"), "").unwrap();
+
+        let directory = SearchDirectory {
+            max_depth: 3,
+            ..SearchDirectory::new(root.to_string_lossy())
+        };
+        assert_eq!(discovered_names(&directory), vec!["app"]);
+    }
+
+    #[test]
+    fn test_discover_custom_and_extension_markers() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("group/dotnet")).unwrap();
+        fs::write(root.join("group/dotnet/App.sln"), "").unwrap();
+        fs::create_dir_all(root.join("group/custom")).unwrap();
+        fs::write(root.join("group/custom/.project"), "").unwrap();
+        fs::create_dir_all(root.join("group/other/deeper")).unwrap();
+
+        let directory = SearchDirectory {
+            max_depth: 3,
+            project_markers: vec![".project".to_string(), "*.sln".to_string()],
+            ..SearchDirectory::new(root.to_string_lossy())
+        };
+        assert_eq!(
+            discovered_names(&directory),
+            vec!["custom", "deeper", "dotnet"]
+        );
+    }
+
+    #[test]
+    fn test_discover_missing_root() {
+        assert_eq!(
+            discover(&SearchDirectory::new("/nonexistent/path")).err(),
+            Some(RootState::Missing)
+        );
+    }
+
+    #[test]
+    fn test_search_directory_input_accepts_string_and_object() {
+        let inputs: Vec<SearchDirectoryInput> = serde_json::from_str(
+            r#"["/a", {"path": "/b", "maxDepth": 3, "projectMarkers": [".git"]}]"#,
+        )
+        .unwrap();
+        let directories = into_directories(inputs);
+        assert_eq!(directories[0], SearchDirectory::new("/a"));
+        assert_eq!(directories[1].max_depth, 3);
+        assert_eq!(directories[1].project_markers, vec![".git".to_string()]);
+    }
+
+    #[test]
+    fn test_discover_skips_default_excludes_and_hidden() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for name in [
+            "app",
+            "node_modules",
+            "target",
+            "__pycache__",
+            ".cache",
+            ".git",
+        ] {
+            fs::create_dir_all(root.join(name)).unwrap();
+        }
+
+        let directory = SearchDirectory::new(root.to_string_lossy());
+        assert_eq!(discovered_names(&directory), vec!["app"]);
+
+        // 关闭隐藏目录过滤并清空排除列表后全部列出
+        let directory = SearchDirectory {
+            exclude: Vec::new(),
+            skip_hidden: false,
+            ..SearchDirectory::new(root.to_string_lossy())
+        };
+        assert_eq!(discovered_names(&directory).len(), 6);
+    }
+
+    #[test]
+    fn test_discover_exclude_prunes_nested_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("archive/old-project")).unwrap();
+        fs::create_dir_all(root.join("work/archive-tool")).unwrap();
+        fs::create_dir_all(root.join("work/service")).unwrap();
+
+        let directory = SearchDirectory {
+            max_depth: 2,
+            exclude: vec!["archive".to_string(), "work/serv*".to_string()],
+            ..SearchDirectory::new(root.to_string_lossy())
+        };
+        assert_eq!(discovered_names(&directory), vec!["archive-tool"]);
+    }
+
+    #[test]
+    fn test_discover_include_filters_workspaces() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("acme/client-web")).unwrap();
+        fs::create_dir_all(root.join("acme/internal")).unwrap();
+        fs::create_dir_all(root.join("client-api")).unwrap();
+
+        // 包含模式不影响中间目录的遍历
+        let directory = SearchDirectory {
+            max_depth: 2,
+            include: vec!["client-*".to_string()],
+            ..SearchDirectory::new(root.to_string_lossy())
+        };
+        fs::write(root.join("client-api/Cargo.toml"), "").unwrap();
+        assert_eq!(
+            discovered_names(&directory),
+            vec!["client-api", "client-web"]
+        );
+
+        let directory = SearchDirectory {
+            include: vec!["acme/*".to_string()],
+            ..directory
+        };
+        assert_eq!(discovered_names(&directory), vec!["client-web", "internal"]);
+    }
+
+    #[test]
+    fn test_search_directory_filter_defaults() {
+        let inputs: Vec<SearchDirectoryInput> = serde_json::from_str(
+            r#"[{"path": "/a"}, {"path": "/b", "exclude": [], "skipHidden": false}]"#,
+        )
+        .unwrap();
+        let directories = into_directories(inputs);
+        assert_eq!(directories[0], SearchDirectory::new("/a"));
+        assert!(directories[1].exclude.is_empty());
+        assert!(!directories[1].skip_hidden);
+    }
+
+    #[test]
+    fn test_discover_respects_gitignore() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for dir in ["app", "vendor", "generated", "dist"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        fs::write(root.join(".gitignore"), "vendor/\ngenerated\n").unwrap();
+        fs::write(root.join(".ignore"), "dist\n").unwrap();
+
+        let directory = SearchDirectory::new(root.to_string_lossy());
+        assert_eq!(discovered_names(&directory), vec!["app"]);
+    }
+
+    #[test]
+    fn test_discover_nested_gitignore_with_negation() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for dir in [
+            "group/build-tools",
+            "group/build-cache",
+            "group/core",
+            "build-root",
+        ] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        // 上层忽略 build-*，下层的 .gitignore 重新包含 build-tools
+        fs::write(root.join(".gitignore"), "build-*\n").unwrap();
+        fs::write(root.join("group/.gitignore"), "!build-tools\n").unwrap();
+
+        let directory = SearchDirectory {
+            max_depth: 2,
+            ..SearchDirectory::new(root.to_string_lossy())
+        };
+        assert_eq!(discovered_names(&directory), vec!["build-tools", "core"]);
+    }
+}
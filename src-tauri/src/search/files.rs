@@ -0,0 +1,452 @@
+//! 文件搜索
+//!
+//! 在搜索目录下逐层列出文件（遵循排除模式、隐藏目录开关和 `.gitignore`/`.ignore`），
+//! 按文件名使用与工作区搜索相同的匹配器（含拼音、模糊匹配与查询操作符）打分排序。
+//! 文件不进入工作区索引，每次搜索时重新遍历，并限制遍历深度和文件总数；
+//! 每个搜索目录的遍历同样设置超时，并随结果返回各目录的状态。同一目录同时只有一次遍历在进行，
+//! 遍历未完成时新的查询等待并复用它（见 `FileWalks`）。
+
+use super::discovery::{into_directories, relative_path, Filters, IgnoreStack};
+use super::query::ParsedQuery;
+use super::roots::{probe_root, PendingResult, RootState, RootStatus};
+use super::tasks::CancelToken;
+use super::{
+    resolve_file_path, score_candidates, sort_hits, FrecencyStore, IndexedWorkspace,
+    SearchDirectory, SearchDirectoryInput, SearchOptions, SearchResults,
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// 默认的文件遍历深度（1 表示只列出搜索目录下的文件）
+pub const DEFAULT_FILE_MAX_DEPTH: usize = 6;
+
+/// 允许配置的文件遍历深度上限
+pub const FILE_MAX_DEPTH_LIMIT: usize = 16;
+
+/// 默认单次搜索最多遍历的文件数
+pub const DEFAULT_MAX_FILES: usize = 20_000;
+
+/// 文件搜索选项
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FileSearchOptions {
+    /// 结果数上限与模糊拼音规则，与工作区搜索相同
+    #[serde(flatten)]
+    pub search: SearchOptions,
+    /// 文件遍历深度，为空时使用默认值
+    pub max_depth: Option<usize>,
+    /// 最多遍历的文件数，为空时使用默认值
+    pub max_files: Option<usize>,
+}
+
+impl FileSearchOptions {
+    /// 实际使用的遍历深度（限制在 1..=FILE_MAX_DEPTH_LIMIT）
+    fn effective_max_depth(&self) -> usize {
+        self.max_depth
+            .unwrap_or(DEFAULT_FILE_MAX_DEPTH)
+            .clamp(1, FILE_MAX_DEPTH_LIMIT)
+    }
+
+    pub(crate) fn effective_max_files(&self) -> usize {
+        self.max_files.unwrap_or(DEFAULT_MAX_FILES)
+    }
+}
+
+/// 单个搜索目录下的文件，或目录无法遍历的原因
+type RootFiles = Result<Arc<Vec<IndexedWorkspace>>, RootState>;
+
+/// 正在后台进行的文件遍历（作为 Tauri 托管状态共享）
+///
+/// 每个搜索目录同时只有一次遍历在进行：遍历未完成时，之后的查询（如下一次按键）等待并复用它，
+/// 避免无响应的目录在每次按键时堆积线程。遍历结果不缓存，完成后的查询重新遍历。
+#[derive(Clone, Default)]
+pub struct FileWalks {
+    pending: Arc<Mutex<HashMap<String, Arc<PendingWalk>>>>,
+}
+
+/// 正在进行的单个搜索目录遍历
+struct PendingWalk {
+    /// 发起遍历时的目录配置
+    config: SearchDirectory,
+    max_depth: usize,
+    /// 本次遍历的文件数上限，不小于上限的查询可以复用
+    limit: usize,
+    result: PendingResult<RootFiles>,
+}
+
+impl FileWalks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 列出搜索目录下的文件（最多 `limit` 个），超时、被取消或目录不可读时返回对应的状态
+    pub(crate) fn root_files(
+        &self,
+        directory: &SearchDirectory,
+        options: &FileSearchOptions,
+        limit: usize,
+        cancel: &CancelToken,
+    ) -> Result<Vec<IndexedWorkspace>, RootState> {
+        let files = self
+            .start_walk(directory, options.effective_max_depth(), limit)
+            .result
+            .wait(options.search.root_timeout(), cancel)
+            .unwrap_or(Err(RootState::TimedOut))?;
+        Ok(files.iter().take(limit).cloned().collect())
+    }
+
+    /// 在后台线程中遍历搜索目录；该目录已有能满足本次查询的遍历在进行时直接复用
+    fn start_walk(
+        &self,
+        directory: &SearchDirectory,
+        max_depth: usize,
+        limit: usize,
+    ) -> Arc<PendingWalk> {
+        let mut pending = match self.pending.lock() {
+            Ok(pending) => pending,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if let Some(walk) = pending.get(&directory.path).filter(|walk| {
+            walk.config == *directory && walk.max_depth == max_depth && walk.limit >= limit
+        }) {
+            return walk.clone();
+        }
+
+        let walk = Arc::new(PendingWalk {
+            config: directory.clone(),
+            max_depth,
+            limit,
+            result: PendingResult::default(),
+        });
+        pending.insert(directory.path.clone(), walk.clone());
+
+        let walks = self.clone();
+        let directory = directory.clone();
+        let thread_walk = walk.clone();
+        std::thread::spawn(move || {
+            let result = probe_root(Path::new(&directory.path))
+                .map(|()| Arc::new(file_entries(&directory, max_depth, limit).collect()));
+            // 先移出进行中的遍历再通知等待者，之后的查询重新遍历
+            if let Ok(mut pending) = walks.pending.lock() {
+                if pending
+                    .get(&directory.path)
+                    .is_some_and(|walk| Arc::ptr_eq(walk, &thread_walk))
+                {
+                    pending.remove(&directory.path);
+                }
+            }
+            thread_walk.result.finish(result);
+        });
+        walk
+    }
+}
+
+/// 在搜索目录中按文件名搜索文件，返回文件的绝对路径
+#[tauri::command]
+pub fn search_files(
+    frecency: tauri::State<'_, FrecencyStore>,
+    walks: tauri::State<'_, FileWalks>,
+    query: String,
+    directories: Vec<SearchDirectoryInput>,
+    options: Option<FileSearchOptions>,
+) -> Result<SearchResults, String> {
+    search_file_entries(
+        &frecency,
+        &walks,
+        &query,
+        &into_directories(directories),
+        &options.unwrap_or_default(),
+    )
+}
+
+/// 用系统默认程序打开文件搜索结果，并记录打开历史；文件必须位于某个搜索目录之内
+#[tauri::command]
+pub fn open_file(
+    frecency: tauri::State<'_, FrecencyStore>,
+    path: String,
+    directories: Vec<SearchDirectoryInput>,
+) -> Result<(), String> {
+    let file_path = resolve_file_path(&path, &into_directories(directories))?;
+    tauri_plugin_opener::open_path(&file_path, None::<&str>)
+        .map_err(|e| format!("打开文件失败: {}", e))?;
+
+    frecency.record_open_best_effort(&file_path.to_string_lossy());
+    Ok(())
+}
+
+pub(crate) fn search_file_entries(
+    frecency: &FrecencyStore,
+    walks: &FileWalks,
+    query: &str,
+    directories: &[SearchDirectory],
+    options: &FileSearchOptions,
+) -> Result<SearchResults, String> {
+    // @别名 之外的搜索目录无需遍历
+    let parsed = ParsedQuery::parse(query);
+    let mut entries: Vec<(&SearchDirectory, IndexedWorkspace)> = Vec::new();
+    let mut roots = Vec::new();
+    for dir in directories.iter().filter(|dir| parsed.includes_root(dir)) {
+        let remaining = options.effective_max_files().saturating_sub(entries.len());
+        let files = walks.root_files(dir, options, remaining, &CancelToken::default());
+        roots.push(RootStatus::from_result(&dir.path, &files, Vec::len));
+        entries.extend(
+            files
+                .unwrap_or_default()
+                .into_iter()
+                .map(|entry| (dir, entry)),
+        );
+    }
+
+    let candidates = entries.iter().map(|(dir, entry)| (*dir, entry));
+    let scored = score_candidates(&frecency.scores(), query, &options.search, candidates);
+    Ok(SearchResults {
+        hits: sort_hits(scored, options.search.effective_limit()),
+        roots,
+    })
+}
+
+/// 列出搜索目录下的文件（最多 `limit` 个）并建立可供匹配器使用的条目
+fn file_entries(
+    directory: &SearchDirectory,
+    max_depth: usize,
+    limit: usize,
+) -> impl Iterator<Item = IndexedWorkspace> {
+    enumerate_files(directory, max_depth, limit)
+        .into_iter()
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?.to_string();
+            Some(IndexedWorkspace::new(&path, name))
+        })
+}
+
+/// 列出搜索目录下的文件，最多 `limit` 个；目录不存在时返回空列表
+pub(crate) fn enumerate_files(
+    directory: &SearchDirectory,
+    max_depth: usize,
+    limit: usize,
+) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    if limit == 0 {
+        return files;
+    }
+    walk_files(directory, max_depth, |path| {
+        files.push(path);
+        if files.len() >= limit {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    });
+    files
+}
+
+/// 逐个访问搜索目录下的文件而不预先收集，`visit` 返回 `Break` 时立即停止遍历
+pub(crate) fn walk_files(
+    directory: &SearchDirectory,
+    max_depth: usize,
+    mut visit: impl FnMut(PathBuf) -> ControlFlow<()>,
+) {
+    let root = PathBuf::from(&directory.path);
+    if !root.is_dir() {
+        return;
+    }
+
+    let walker = FileWalker {
+        root: &root,
+        max_depth,
+        filters: Filters::new(directory),
+    };
+    let _ = walker.walk(&root, 1, &mut IgnoreStack::default(), &mut visit);
+}
+
+struct FileWalker<'a> {
+    root: &'a Path,
+    max_depth: usize,
+    filters: Filters,
+}
+
+impl FileWalker<'_> {
+    fn walk(
+        &self,
+        dir: &Path,
+        depth: usize,
+        ignores: &mut IgnoreStack,
+        visit: &mut dyn FnMut(PathBuf) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return ControlFlow::Continue(());
+        };
+        let pushed = ignores.push_dir(dir);
+
+        // 先处理当前目录的文件，再进入子目录，提前停止时浅层文件优先
+        let mut subdirs = Vec::new();
+        let mut flow = ControlFlow::Continue(());
+        for entry in entries.flatten() {
+            // 不跟随符号链接，避免循环
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let Ok(name) = entry.file_name().into_string() else {
+                continue;
+            };
+            let path = entry.path();
+            let relative = relative_path(self.root, &path);
+            if self.filters.is_excluded(&name, &relative)
+                || ignores.is_ignored(&path, file_type.is_dir())
+            {
+                continue;
+            }
+
+            if file_type.is_file() {
+                flow = visit(path);
+                if flow.is_break() {
+                    break;
+                }
+            } else if file_type.is_dir() && depth < self.max_depth {
+                subdirs.push(path);
+            }
+        }
+
+        if flow.is_continue() {
+            subdirs.sort();
+            for subdir in subdirs {
+                flow = self.walk(&subdir, depth + 1, ignores, visit);
+                if flow.is_break() {
+                    break;
+                }
+            }
+        }
+
+        if pushed {
+            ignores.pop();
+        }
+        flow
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::MatchKind;
+    use tempfile::TempDir;
+
+    fn file_names(files: &[PathBuf]) -> Vec<String> {
+        let mut names: Vec<String> = files
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        names
+    }
+
+    fn project_tree() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("shop/deploy")).unwrap();
+        fs::create_dir_all(root.join("shop/node_modules/lib")).unwrap();
+        fs::create_dir_all(root.join("shop/dist")).unwrap();
+        fs::write(root.join("shop/docker-compose.yml"), "").unwrap();
+        fs::write(root.join("shop/deploy/项目说明.md"), "").unwrap();
+        fs::write(root.join("shop/node_modules/lib/index.js"), "").unwrap();
+        fs::write(root.join("shop/dist/bundle.js"), "").unwrap();
+        fs::write(root.join("shop/.gitignore"), "dist/\n").unwrap();
+        temp_dir
+    }
+
+    #[test]
+    fn test_enumerate_files_respects_filters_and_ignore_files() {
+        let temp_dir = project_tree();
+        let directory = SearchDirectory::new(temp_dir.path().to_string_lossy());
+        let files = enumerate_files(&directory, DEFAULT_FILE_MAX_DEPTH, DEFAULT_MAX_FILES);
+        // 隐藏文件、node_modules 与 .gitignore 中的 dist 都被跳过
+        assert_eq!(
+            file_names(&files),
+            vec!["docker-compose.yml", "项目说明.md"]
+        );
+    }
+
+    #[test]
+    fn test_enumerate_files_depth_and_limit() {
+        let temp_dir = project_tree();
+        let directory = SearchDirectory::new(temp_dir.path().to_string_lossy());
+        assert_eq!(
+            file_names(&enumerate_files(&directory, 2, DEFAULT_MAX_FILES)),
+            vec!["docker-compose.yml"]
+        );
+        assert_eq!(
+            enumerate_files(&directory, DEFAULT_FILE_MAX_DEPTH, 1).len(),
+            1
+        );
+        assert!(enumerate_files(&SearchDirectory::new("/nonexistent/path"), 3, 10).is_empty());
+    }
+
+    #[test]
+    fn test_file_walks_share_in_flight_walk() {
+        let temp_dir = project_tree();
+        let directory = SearchDirectory::new(temp_dir.path().to_string_lossy());
+        let walks = FileWalks::new();
+        let mut options = FileSearchOptions::default();
+        options.search.root_timeout_ms = Some(20);
+
+        // 模拟无响应目录上尚未完成的遍历：后续查询等待它而不是再启动新的遍历
+        let hung = Arc::new(PendingWalk {
+            config: directory.clone(),
+            max_depth: options.effective_max_depth(),
+            limit: 100,
+            result: PendingResult::default(),
+        });
+        walks
+            .pending
+            .lock()
+            .unwrap()
+            .insert(directory.path.clone(), hung.clone());
+        let cancel = CancelToken::default();
+        for _ in 0..3 {
+            let files = walks.root_files(&directory, &options, 10, &cancel);
+            assert_eq!(files.unwrap_err(), RootState::TimedOut);
+        }
+        assert!(Arc::ptr_eq(
+            &walks.pending.lock().unwrap()[&directory.path],
+            &hung
+        ));
+
+        // 遍历完成后从进行中移出，之后的查询重新遍历
+        hung.result.finish(Ok(Arc::new(Vec::new())));
+        walks.pending.lock().unwrap().clear();
+        let files = walks.root_files(&directory, &options, 1, &cancel).unwrap();
+        assert_eq!(files.len(), 1);
+    }
+
+    #[test]
+    fn test_search_files_ranks_with_workspace_matchers() {
+        let temp_dir = project_tree();
+        let directories = vec![SearchDirectory::new(temp_dir.path().to_string_lossy())];
+        let search = |query: &str| {
+            search_file_entries(
+                &FrecencyStore::in_memory(),
+                &FileWalks::new(),
+                query,
+                &directories,
+                &FileSearchOptions::default(),
+            )
+            .unwrap()
+            .hits
+        };
+
+        let results = search("dockercompose");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "docker-compose.yml");
+        assert_eq!(results[0].match_kind, Some(MatchKind::Normalized));
+        assert!(Path::new(&results[0].path).is_absolute());
+        assert!(results[0].path.ends_with("docker-compose.yml"));
+
+        // 拼音首字母匹配中文文件名
+        let results = search("xmsm");
+        assert_eq!(results[0].name, "项目说明.md");
+        assert_eq!(results[0].match_kind, Some(MatchKind::PinyinInitials));
+    }
+}
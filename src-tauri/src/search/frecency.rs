@@ -0,0 +1,206 @@
+//! 工作区打开记录（frecency）
+//!
+//! 记录每个工作区的打开次数和最近打开时间，持久化到 `~/.ytools/frecency.json`，
+//! 按“频率 × 时间衰减”计算得分参与搜索排序。
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 记录文件名（位于 .ytools 目录）
+const FRECENCY_FILE: &str = "frecency.json";
+
+/// 超过该时长未打开的记录在保存时清理（90 天）
+const RECORD_TTL_SECS: u64 = 90 * 24 * 3600;
+
+/// 参与搜索排序时 frecency 加分的上限，避免压过匹配质量
+pub const FRECENCY_BONUS_CAP: i64 = 250;
+
+/// 单个工作区的打开记录
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VisitRecord {
+    /// 累计打开次数
+    pub count: u32,
+    /// 最近一次打开时间（Unix 秒）
+    pub last_opened: u64,
+}
+
+impl VisitRecord {
+    /// frecency 原始得分：打开次数 × 按距今时长衰减的权重
+    pub fn frecency(&self, now: u64) -> i64 {
+        let age = now.saturating_sub(self.last_opened);
+        let weight = if age < 3600 {
+            100 // 一小时内
+        } else if age < 24 * 3600 {
+            50 // 一天内
+        } else if age < 7 * 24 * 3600 {
+            25 // 一周内
+        } else {
+            10
+        };
+        i64::from(self.count) * weight
+    }
+}
+
+/// 打开记录存储（作为 Tauri 托管状态共享）
+pub struct FrecencyStore {
+    /// 持久化文件路径，为 None 时仅保存在内存中
+    file: Option<PathBuf>,
+    records: Mutex<HashMap<String, VisitRecord>>,
+}
+
+impl FrecencyStore {
+    /// 从 `~/.ytools/frecency.json` 加载，无法定位目录时退化为内存存储
+    pub fn load_default() -> Self {
+        match crate::notes::get_ytools_dir() {
+            Ok(dir) => Self::load(dir.join(FRECENCY_FILE)),
+            Err(_) => Self::in_memory(),
+        }
+    }
+
+    /// 从指定文件加载，文件不存在或内容损坏时从空记录开始
+    pub fn load(file: PathBuf) -> Self {
+        let records = fs::read_to_string(&file)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self {
+            file: Some(file),
+            records: Mutex::new(records),
+        }
+    }
+
+    /// 不落盘的内存存储
+    pub fn in_memory() -> Self {
+        Self {
+            file: None,
+            records: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 记录一次打开并保存
+    pub fn record_open(&self, path: &str) -> Result<(), String> {
+        self.record_open_at(path, now_secs())
+    }
+
+    /// 记录一次打开；打开记录仅影响排序，保存失败不影响打开本身，因此忽略错误
+    pub fn record_open_best_effort(&self, path: &str) {
+        let _ = self.record_open(path);
+    }
+
+    fn record_open_at(&self, path: &str, now: u64) -> Result<(), String> {
+        let mut records = self
+            .records
+            .lock()
+            .map_err(|_| "打开记录已损坏".to_string())?;
+        let record = records.entry(path.to_string()).or_insert(VisitRecord {
+            count: 0,
+            last_opened: now,
+        });
+        record.count = record.count.saturating_add(1);
+        record.last_opened = now;
+
+        // 清理长期未打开的记录
+        records.retain(|_, record| now.saturating_sub(record.last_opened) < RECORD_TTL_SECS);
+
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+        let content = serde_json::to_string_pretty(&*records)
+            .map_err(|e| format!("序列化打开记录失败: {}", e))?;
+        fs::write(file, content).map_err(|e| format!("保存打开记录失败: {}", e))
+    }
+
+    /// 获取所有记录在当前时刻的 frecency 原始得分
+    pub fn scores(&self) -> HashMap<String, i64> {
+        let now = now_secs();
+        self.records
+            .lock()
+            .map(|records| {
+                records
+                    .iter()
+                    .map(|(path, record)| (path.clone(), record.frecency(now)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// 将 frecency 原始得分换算为搜索排序加分
+pub fn frecency_bonus(frecency: i64) -> i64 {
+    (frecency / 4).min(FRECENCY_BONUS_CAP)
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_frecency_decays_with_age() {
+        let now = 10_000_000;
+        let record = |age: u64| VisitRecord {
+            count: 4,
+            last_opened: now - age,
+        };
+        assert_eq!(record(60).frecency(now), 400);
+        assert_eq!(record(2 * 3600).frecency(now), 200);
+        assert_eq!(record(3 * 24 * 3600).frecency(now), 100);
+        assert_eq!(record(30 * 24 * 3600).frecency(now), 40);
+    }
+
+    #[test]
+    fn test_record_open_counts_and_persists() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join(FRECENCY_FILE);
+
+        let store = FrecencyStore::load(file.clone());
+        store.record_open("/code/a").unwrap();
+        store.record_open("/code/a").unwrap();
+        store.record_open("/code/b").unwrap();
+
+        // 重新加载后记录仍然存在
+        let reloaded = FrecencyStore::load(file);
+        let scores = reloaded.scores();
+        assert_eq!(scores.len(), 2);
+        assert!(scores["/code/a"] > scores["/code/b"]);
+    }
+
+    #[test]
+    fn test_record_open_prunes_expired_records() {
+        let store = FrecencyStore::in_memory();
+        store.record_open_at("/old", 0).unwrap();
+        store.record_open_at("/new", RECORD_TTL_SECS + 1).unwrap();
+
+        let records = store.records.lock().unwrap();
+        assert!(!records.contains_key("/old"));
+        assert!(records.contains_key("/new"));
+    }
+
+    #[test]
+    fn test_load_corrupted_file_starts_empty() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join(FRECENCY_FILE);
+        fs::write(&file, "not json").unwrap();
+
+        assert!(FrecencyStore::load(file).scores().is_empty());
+    }
+
+    #[test]
+    fn test_frecency_bonus_is_capped() {
+        assert_eq!(frecency_bonus(0), 0);
+        assert_eq!(frecency_bonus(400), 100);
+        assert_eq!(frecency_bonus(100_000), FRECENCY_BONUS_CAP);
+    }
+}
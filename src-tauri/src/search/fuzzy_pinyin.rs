@@ -0,0 +1,154 @@
+//! 模糊拼音
+//!
+//! 兼容方言口音常见的混淆：声母 `zh/z`、`ch/c`、`sh/s`、`n/l`，韵母 `an/ang`、`en/eng`、`in/ing`。
+//! 建立索引时为每个音节预先生成所有模糊拼写并标记其依赖的规则，
+//! 匹配时只使用用户开启的规则对应的拼写，默认全部关闭。
+
+use serde::Deserialize;
+
+/// 模糊拼音规则开关（每条规则双向生效）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FuzzyPinyinRules {
+    pub zh_z: bool,
+    pub ch_c: bool,
+    pub sh_s: bool,
+    pub n_l: bool,
+    pub an_ang: bool,
+    pub en_eng: bool,
+    pub in_ing: bool,
+}
+
+const ZH_Z: u8 = 1 << 0;
+const CH_C: u8 = 1 << 1;
+const SH_S: u8 = 1 << 2;
+const N_L: u8 = 1 << 3;
+const AN_ANG: u8 = 1 << 4;
+const EN_ENG: u8 = 1 << 5;
+const IN_ING: u8 = 1 << 6;
+
+/// 声母规则：(规则, 一侧, 另一侧)
+const INITIAL_RULES: &[(u8, &str, &str)] = &[
+    (ZH_Z, "zh", "z"),
+    (CH_C, "ch", "c"),
+    (SH_S, "sh", "s"),
+    (N_L, "n", "l"),
+];
+
+/// 韵母规则：(规则, 一侧, 另一侧)，较长的一侧在前以免 `ang` 被当作 `an` 处理
+const FINAL_RULES: &[(u8, &str, &str)] = &[
+    (AN_ANG, "ang", "an"),
+    (EN_ENG, "eng", "en"),
+    (IN_ING, "ing", "in"),
+];
+
+impl FuzzyPinyinRules {
+    /// 已开启规则的位掩码
+    pub(crate) fn mask(&self) -> u8 {
+        [
+            (self.zh_z, ZH_Z),
+            (self.ch_c, CH_C),
+            (self.sh_s, SH_S),
+            (self.n_l, N_L),
+            (self.an_ang, AN_ANG),
+            (self.en_eng, EN_ENG),
+            (self.in_ing, IN_ING),
+        ]
+        .iter()
+        .filter(|(enabled, _)| *enabled)
+        .fold(0, |mask, (_, bit)| mask | bit)
+    }
+}
+
+/// 生成音节的所有模糊拼写（不含原拼写），返回 (拼写, 依赖的规则掩码)
+pub(crate) fn fuzzy_spellings(syllable: &str) -> Vec<(String, u8)> {
+    let swapped = INITIAL_RULES.iter().find_map(|&(rule, a, b)| {
+        if syllable.starts_with(a) {
+            Some((rule, a, b))
+        } else if syllable.starts_with(b) && !is_retroflex_prefix(syllable, b) {
+            Some((rule, b, a))
+        } else {
+            None
+        }
+    });
+    let (initials, rest): (Vec<(String, u8)>, &str) = match swapped {
+        Some((rule, from, to)) => (
+            vec![(from.to_string(), 0), (to.to_string(), rule)],
+            &syllable[from.len()..],
+        ),
+        None => (vec![(String::new(), 0)], syllable),
+    };
+
+    let mut finals: Vec<(String, u8)> = vec![(rest.to_string(), 0)];
+    for &(rule, a, b) in FINAL_RULES {
+        let (from, to) = if rest.ends_with(a) {
+            (a, b)
+        } else if rest.ends_with(b) {
+            (b, a)
+        } else {
+            continue;
+        };
+        let stem = &rest[..rest.len() - from.len()];
+        finals.push((format!("{}{}", stem, to), rule));
+        break;
+    }
+
+    let mut spellings = Vec::new();
+    for (initial, initial_rule) in &initials {
+        for (fin, final_rule) in &finals {
+            let rules = initial_rule | final_rule;
+            if rules != 0 {
+                spellings.push((format!("{}{}", initial, fin), rules));
+            }
+        }
+    }
+    spellings
+}
+
+/// `z`/`c`/`s` 后紧跟 `h` 时属于翘舌音，已由 zh/ch/sh 一侧处理
+fn is_retroflex_prefix(syllable: &str, initial: &str) -> bool {
+    matches!(initial, "z" | "c" | "s") && syllable[initial.len()..].starts_with('h')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spellings(syllable: &str) -> Vec<String> {
+        let mut result: Vec<String> = fuzzy_spellings(syllable)
+            .into_iter()
+            .map(|(s, _)| s)
+            .collect();
+        result.sort();
+        result
+    }
+
+    #[test]
+    fn test_fuzzy_spellings_initials_and_finals() {
+        assert_eq!(spellings("zhong"), vec!["zong"]);
+        assert_eq!(spellings("xin"), vec!["xing"]);
+        assert_eq!(spellings("shan"), vec!["san", "sang", "shang"]);
+        assert_eq!(spellings("lan"), vec!["lang", "nan", "nang"]);
+        assert_eq!(spellings("ceng"), vec!["cen", "chen", "cheng"]);
+        assert!(spellings("ma").is_empty());
+    }
+
+    #[test]
+    fn test_fuzzy_spellings_rule_masks() {
+        let rules: Vec<(String, u8)> = fuzzy_spellings("zhang");
+        assert!(rules.contains(&("zang".to_string(), ZH_Z)));
+        assert!(rules.contains(&("zhan".to_string(), AN_ANG)));
+        assert!(rules.contains(&("zan".to_string(), ZH_Z | AN_ANG)));
+    }
+
+    #[test]
+    fn test_rules_mask() {
+        assert_eq!(FuzzyPinyinRules::default().mask(), 0);
+        let rules = FuzzyPinyinRules {
+            zh_z: true,
+            in_ing: true,
+            ..Default::default()
+        };
+        assert_eq!(rules.mask(), ZH_Z | IN_ING);
+    }
+}
@@ -0,0 +1,255 @@
+//! Git 仓库状态
+//!
+//! 直接读取工作区中的 `.git` 目录（不访问网络，也不调用 git 命令），获取当前分支、是否有未提交的改动
+//! 以及相对上游分支的领先/落后提交数。只为返回给界面的搜索结果计算，结果按工作区路径缓存一段时间，
+//! 避免每次按键都重新检查工作区文件。搜索本身只使用缓存，缺失或过期的状态在后台读取，
+//! 完成后通过 `git-status` 事件推送。
+
+use git2::{BranchType, Repository, StatusOptions};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 后台读取完成事件
+pub const GIT_STATUS_EVENT: &str = "git-status";
+
+/// 缓存的有效期，过期后下次查询时在后台重新读取
+const GIT_STATUS_TTL: Duration = Duration::from_secs(10);
+
+/// 短提交号的长度
+const SHORT_COMMIT_LEN: usize = 7;
+
+/// 工作区的 Git 状态
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitStatus {
+    /// 当前分支，HEAD 分离时为空
+    pub branch: Option<String>,
+    /// HEAD 指向的提交（短哈希），仓库尚无提交时为空
+    pub commit: Option<String>,
+    /// 是否有未提交的改动（含未跟踪的文件）
+    pub dirty: bool,
+    /// 上游分支（如 `origin/main`），未设置时为空
+    pub upstream: Option<String>,
+    /// 本地领先上游的提交数
+    pub ahead: usize,
+    /// 本地落后上游的提交数
+    pub behind: usize,
+}
+
+impl GitStatus {
+    /// 读取工作区的 Git 状态，不是 Git 仓库（`.git` 不在工作区根目录）时返回 None
+    pub(crate) fn read(path: &Path) -> Option<Self> {
+        let repo = Repository::open(path).ok()?;
+        let head = repo.find_reference("HEAD").ok()?;
+        let branch = head
+            .symbolic_target()
+            .and_then(|target| target.strip_prefix("refs/heads/"))
+            .map(str::to_string);
+        let head_oid = repo.head().ok().and_then(|head| head.target());
+
+        let mut status = Self {
+            commit: head_oid.map(|oid| oid.to_string()[..SHORT_COMMIT_LEN].to_string()),
+            dirty: is_dirty(&repo),
+            ..Self::default()
+        };
+
+        // 只有本地分支且已有提交时才比较上游
+        if let (Some(name), Some(local)) = (&branch, head_oid) {
+            let upstream = repo
+                .find_branch(name, BranchType::Local)
+                .and_then(|branch| branch.upstream())
+                .ok();
+            if let Some(upstream) = upstream {
+                status.upstream = upstream.name().ok().flatten().map(str::to_string);
+                if let Some(remote) = upstream.get().target() {
+                    if let Ok((ahead, behind)) = repo.graph_ahead_behind(local, remote) {
+                        status.ahead = ahead;
+                        status.behind = behind;
+                    }
+                }
+            }
+        }
+        status.branch = branch;
+        Some(status)
+    }
+}
+
+/// `git-status` 事件中单个工作区的状态
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitStatusUpdate {
+    pub path: String,
+    /// 不是 Git 仓库时为空
+    pub git: Option<GitStatus>,
+}
+
+/// 工作区或暂存区是否有改动；不展开未跟踪的目录、不检查子模块，保证大仓库也足够快
+fn is_dirty(repo: &Repository) -> bool {
+    let mut options = StatusOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(false)
+        .exclude_submodules(true);
+    repo.statuses(Some(&mut options))
+        .is_ok_and(|statuses| !statuses.is_empty())
+}
+
+/// 按工作区路径缓存的 Git 状态
+#[derive(Default)]
+pub(crate) struct GitStatusCache {
+    entries: Mutex<HashMap<String, (Instant, Option<GitStatus>)>>,
+    /// 正在后台读取的工作区
+    reading: Mutex<HashSet<String>>,
+}
+
+impl GitStatusCache {
+    /// 缓存中的 Git 状态（已过期的也返回），不读取仓库
+    pub fn cached(&self, path: &str) -> Option<GitStatus> {
+        self.entries
+            .lock()
+            .ok()
+            .and_then(|entries| entries.get(path).and_then(|(_, status)| status.clone()))
+    }
+
+    /// 选出缓存缺失或过期、且不在读取中的工作区，并登记为读取中
+    pub fn claim_stale<'a>(&self, paths: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+        let (Ok(entries), Ok(mut reading)) = (self.entries.lock(), self.reading.lock()) else {
+            return Vec::new();
+        };
+        paths
+            .into_iter()
+            .filter(|path| {
+                entries
+                    .get(*path)
+                    .is_none_or(|(read_at, _)| read_at.elapsed() >= GIT_STATUS_TTL)
+            })
+            .filter(|path| reading.insert(path.to_string()))
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// 读取工作区的 Git 状态并写入缓存，同时移出读取中
+    pub fn load(&self, path: &str) -> Option<GitStatus> {
+        // 读取期间不持有锁，避免慢仓库阻塞其他查询
+        let status = GitStatus::read(Path::new(path));
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(path.to_string(), (Instant::now(), status.clone()));
+        }
+        if let Ok(mut reading) = self.reading.lock() {
+            reading.remove(path);
+        }
+        status
+    }
+
+    /// 只保留满足条件的工作区（如仍在某个搜索目录下的）
+    pub fn retain(&self, keep: impl Fn(&str) -> bool) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.retain(|path, _| keep(path));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::{RepositoryInitOptions, Signature};
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn commit(repo: &Repository, message: &str) -> git2::Oid {
+        let signature = Signature::now("test", "test@example.com").unwrap();
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let parents: Vec<git2::Commit> = repo
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_commit().ok())
+            .into_iter()
+            .collect();
+        let parents: Vec<&git2::Commit> = parents.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .unwrap()
+    }
+
+    fn init_repo(dir: &Path) -> Repository {
+        let mut options = RepositoryInitOptions::new();
+        options.initial_head("main");
+        Repository::init_opts(dir, &options).unwrap()
+    }
+
+    #[test]
+    fn test_read_branch_dirty_and_ahead_behind() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = init_repo(temp_dir.path());
+
+        // 尚无提交的仓库也能读取分支
+        let status = GitStatus::read(temp_dir.path()).unwrap();
+        assert_eq!(status.branch.as_deref(), Some("main"));
+        assert_eq!(status.commit, None);
+
+        fs::write(temp_dir.path().join("README.md"), "hello").unwrap();
+        let first = commit(&repo, "first");
+        repo.branch("base", &repo.find_commit(first).unwrap(), false)
+            .unwrap();
+        repo.find_branch("main", BranchType::Local)
+            .unwrap()
+            .set_upstream(Some("base"))
+            .unwrap();
+        fs::write(temp_dir.path().join("README.md"), "hello again").unwrap();
+        let second = commit(&repo, "second");
+
+        let status = GitStatus::read(temp_dir.path()).unwrap();
+        assert_eq!(status.branch.as_deref(), Some("main"));
+        assert_eq!(
+            status.commit.as_deref(),
+            Some(&second.to_string()[..SHORT_COMMIT_LEN])
+        );
+        assert!(!status.dirty);
+        assert_eq!(status.upstream.as_deref(), Some("base"));
+        assert_eq!((status.ahead, status.behind), (1, 0));
+
+        // 未跟踪的文件也算作改动
+        fs::write(temp_dir.path().join("notes.txt"), "").unwrap();
+        assert!(GitStatus::read(temp_dir.path()).unwrap().dirty);
+
+        // HEAD 分离时没有分支
+        repo.set_head_detached(first).unwrap();
+        let status = GitStatus::read(temp_dir.path()).unwrap();
+        assert_eq!(status.branch, None);
+        assert_eq!(status.upstream, None);
+    }
+
+    #[test]
+    fn test_non_repository_and_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().to_string_lossy().to_string();
+        let cache = GitStatusCache::default();
+        assert_eq!(cache.claim_stale([path.as_str()]), vec![path.clone()]);
+        // 读取中的工作区不会重复登记
+        assert!(cache.claim_stale([path.as_str()]).is_empty());
+        assert_eq!(cache.load(&path), None);
+
+        // 有效期内使用缓存，不会察觉刚初始化的仓库
+        init_repo(temp_dir.path());
+        assert!(cache.claim_stale([path.as_str()]).is_empty());
+        assert_eq!(cache.cached(&path), None);
+        cache.retain(|_| false);
+        assert_eq!(cache.claim_stale([path.as_str()]), vec![path.clone()]);
+        assert!(cache.load(&path).is_some());
+        assert!(cache.cached(&path).is_some());
+    }
+}
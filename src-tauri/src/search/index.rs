@@ -0,0 +1,662 @@
+//! 工作区索引
+//!
+//! 为每个搜索目录缓存其下发现的工作区及预先计算好的匹配形式（小写、规范化、拼音全拼/首字母），
+//! 搜索时直接读取索引而不是每次按键都重新遍历目录；目录发生增删改名时由文件监听将其标记为失效，
+//! 下次查询时重建。项目类型等信息（见 `project` 模块）在重建时一并计算。重建在后台线程中进行并设置超时（见 `roots` 模块），无响应的目录不会阻塞搜索。
+
+use super::discovery::{discover, SearchDirectory};
+use super::git::{GitStatus, GitStatusCache, GitStatusUpdate};
+use super::project::ProjectInfo;
+use super::roots::{PendingResult, RootState};
+use super::scoring::{lower_char, word_starts};
+use super::syllables::{char_readings, CharReadings};
+use super::tasks::CancelToken;
+use super::{normalize, to_pinyin_full_multi, to_pinyin_initials_multi, PinyinVariant};
+use notify::event::ModifyKind;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock, Weak};
+use std::time::Duration;
+
+/// 索引中的单个工作区
+#[derive(Debug, Clone)]
+pub(crate) struct IndexedWorkspace {
+    /// 工作区文件夹的绝对路径
+    pub path: String,
+    /// 显示名称（文件夹名）
+    pub name: String,
+    /// 逐字符小写的名称（与原名称按字符一一对应）
+    pub lower_chars: Vec<char>,
+    /// 每个字符是否为单词开头
+    pub word_starts: Vec<bool>,
+    /// 规范化后的名称
+    pub name_norm: String,
+    /// `name_norm` 中每个字符来源于原名称的第几个字符
+    pub norm_sources: Vec<usize>,
+    /// 拼音全拼（有上限的多音字组合，仅用于模糊匹配）
+    pub pinyin_full: Vec<PinyinVariant>,
+    /// 拼音首字母（有上限的多音字组合，仅用于模糊匹配）
+    pub pinyin_initials: Vec<PinyinVariant>,
+    /// 每个字符的可选读法（读音网格），用于全拼、首字母与混合匹配
+    pub readings: Vec<CharReadings>,
+    /// 项目类型、主要语言等信息（仅工作区，文件搜索的条目为空）
+    pub project: ProjectInfo,
+}
+
+impl IndexedWorkspace {
+    pub(crate) fn new(path: &Path, name: String) -> Self {
+        let chars: Vec<char> = name.chars().collect();
+        let lower_chars: Vec<char> = chars.iter().copied().map(lower_char).collect();
+
+        // 逐字符规范化，记录每个规范化字符的来源位置
+        let mut name_norm = String::new();
+        let mut norm_sources = Vec::new();
+        for (index, c) in lower_chars.iter().enumerate() {
+            for n in normalize(c.encode_utf8(&mut [0; 4])).chars() {
+                name_norm.push(n);
+                norm_sources.push(index);
+            }
+        }
+
+        Self {
+            path: path.to_string_lossy().to_string(),
+            word_starts: word_starts(&chars),
+            lower_chars,
+            name_norm,
+            norm_sources,
+            pinyin_full: to_pinyin_full_multi(&name),
+            pinyin_initials: to_pinyin_initials_multi(&name),
+            readings: char_readings(&name),
+            project: ProjectInfo::default(),
+            name,
+        }
+    }
+
+    pub(crate) fn with_project(mut self, project: ProjectInfo) -> Self {
+        self.project = project;
+        self
+    }
+}
+
+/// 单个搜索目录的索引
+struct RootIndex {
+    /// 建立索引时使用的目录配置，配置变化时需要重建
+    config: SearchDirectory,
+    /// 搜索目录路径，用于匹配文件监听事件
+    path: PathBuf,
+    /// 目录下的工作区
+    entries: Arc<Vec<IndexedWorkspace>>,
+    /// 正在监听的目录（搜索目录本身及遍历过的中间目录）
+    watched: Vec<PathBuf>,
+    /// 是否已失效（监听到变化后置为 true，下次查询时重建）
+    stale: bool,
+}
+
+/// 单个搜索目录的工作区，或目录无法遍历的原因
+pub(crate) type RootEntries = Result<Arc<Vec<IndexedWorkspace>>, RootState>;
+
+/// 正在后台进行的目录重建，同一目录的多次查询共享同一次重建
+struct PendingBuild {
+    /// 发起重建时的目录配置
+    config: SearchDirectory,
+    result: PendingResult<RootEntries>,
+}
+
+/// 文件监听器及各目录被引用的次数
+///
+/// 嵌套或重叠的搜索目录可能遍历到同一个目录，只有不再被任何搜索目录（或进行中的重建）引用时才停止监听。
+#[derive(Default)]
+struct WatchState {
+    /// 首次建立索引时创建；创建失败时索引仍可用，只是不会自动刷新
+    watcher: Option<RecommendedWatcher>,
+    /// 每个监听目录被引用的次数
+    counts: HashMap<PathBuf, usize>,
+}
+
+struct IndexShared {
+    /// 以配置中的目录字符串为键的索引
+    roots: RwLock<HashMap<String, RootIndex>>,
+    /// 文件监听器
+    watches: Mutex<WatchState>,
+    /// 正在重建的目录
+    pending: Mutex<HashMap<String, Arc<PendingBuild>>>,
+    /// 搜索结果中工作区的 Git 状态
+    git: GitStatusCache,
+    /// 每个搜索目录监听到变化的次数，重建期间有变化时重建结果仍标记为失效；
+    /// 目录从配置中移除时一并删除，此后完成的重建不再写入索引
+    generations: Mutex<HashMap<String, u64>>,
+}
+
+/// 工作区索引（作为 Tauri 托管状态在各次搜索之间共享，克隆得到的句柄指向同一份索引）
+#[derive(Clone)]
+pub struct WorkspaceIndex {
+    shared: Arc<IndexShared>,
+}
+
+impl Default for WorkspaceIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WorkspaceIndex {
+    pub fn new() -> Self {
+        Self {
+            shared: Arc::new(IndexShared {
+                roots: RwLock::new(HashMap::new()),
+                watches: Mutex::new(WatchState::default()),
+                pending: Mutex::new(HashMap::new()),
+                git: GitStatusCache::default(),
+                generations: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    /// 获取各搜索目录的索引快照，缺失、失效或选项变化的目录会先重建
+    ///
+    /// 不再出现在 `directories` 中的目录会被移出索引并停止监听。
+    pub(crate) fn snapshot(
+        &self,
+        directories: &[SearchDirectory],
+        timeout: Duration,
+    ) -> Vec<(String, RootEntries)> {
+        self.prune(directories);
+
+        directories
+            .iter()
+            .map(|dir| {
+                let entries = self.root_entries(dir, timeout, &CancelToken::default());
+                (dir.path.clone(), entries)
+            })
+            .collect()
+    }
+
+    /// 获取单个搜索目录下的工作区，缺失、失效或选项变化时先重建
+    ///
+    /// 重建超过 `timeout` 仍未完成（或等待期间请求被取消）时返回 `TimedOut`，重建在后台继续，
+    /// 完成后写入索引供后续查询使用。
+    pub(crate) fn root_entries(
+        &self,
+        dir: &SearchDirectory,
+        timeout: Duration,
+        cancel: &CancelToken,
+    ) -> RootEntries {
+        let cached = self.shared.roots.read().ok().and_then(|roots| {
+            roots
+                .get(&dir.path)
+                .filter(|root| !root.stale && root.config == *dir)
+                .map(|root| root.entries.clone())
+        });
+        if let Some(entries) = cached {
+            return Ok(entries);
+        }
+
+        self.start_rebuild(dir)
+            .result
+            .wait(timeout, cancel)
+            .unwrap_or(Err(RootState::TimedOut))
+    }
+
+    /// 在后台线程中重建目录索引；该目录已有相同配置的重建在进行时直接复用，
+    /// 避免无响应的目录在每次按键时堆积线程
+    fn start_rebuild(&self, dir: &SearchDirectory) -> Arc<PendingBuild> {
+        let mut pending = match self.shared.pending.lock() {
+            Ok(pending) => pending,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if let Some(build) = pending.get(&dir.path).filter(|build| build.config == *dir) {
+            return build.clone();
+        }
+
+        let build = Arc::new(PendingBuild {
+            config: dir.clone(),
+            result: PendingResult::default(),
+        });
+        pending.insert(dir.path.clone(), build.clone());
+
+        let index = self.clone();
+        let dir = dir.clone();
+        let thread_build = build.clone();
+        std::thread::spawn(move || {
+            let result = index.rebuild(&dir);
+            // 先移出进行中的重建再通知等待者，之后的查询不会复用已完成（可能已过期）的结果
+            if let Ok(mut pending) = index.shared.pending.lock() {
+                if pending
+                    .get(&dir.path)
+                    .is_some_and(|build| Arc::ptr_eq(build, &thread_build))
+                {
+                    pending.remove(&dir.path);
+                }
+            }
+            thread_build.result.finish(result);
+        });
+        build
+    }
+
+    /// 缓存中工作区的 Git 状态（已过期的也返回），不读取仓库
+    pub(crate) fn git_status(&self, path: &str) -> Option<GitStatus> {
+        self.shared.git.cached(path)
+    }
+
+    /// 在后台线程中读取缓存缺失或过期的 Git 状态，全部读取完成后调用一次 `on_ready`；
+    /// 没有需要读取的工作区（或都已在读取中）时不调用
+    pub(crate) fn refresh_git_status<'a>(
+        &self,
+        paths: impl IntoIterator<Item = &'a str>,
+        on_ready: impl FnOnce(Vec<GitStatusUpdate>) + Send + 'static,
+    ) {
+        let paths = self.shared.git.claim_stale(paths);
+        if paths.is_empty() {
+            return;
+        }
+        let index = self.clone();
+        std::thread::spawn(move || {
+            let updates = paths
+                .into_iter()
+                .map(|path| GitStatusUpdate {
+                    git: index.shared.git.load(&path),
+                    path,
+                })
+                .collect();
+            on_ready(updates);
+        });
+    }
+
+    /// 将包含 `path` 的搜索目录标记为失效
+    pub(crate) fn invalidate(&self, path: &Path) {
+        mark_stale(&self.shared, path);
+    }
+
+    /// 重新遍历搜索目录并写入索引，目录不存在或不可读时移出索引
+    fn rebuild(&self, dir: &SearchDirectory) -> RootEntries {
+        let generation = match self.shared.generations.lock() {
+            Ok(mut generations) => *generations.entry(dir.path.clone()).or_insert(0),
+            Err(_) => 0,
+        };
+
+        let discovery = match discover(dir) {
+            Ok(discovery) => discovery,
+            Err(state) => {
+                let removed = match self.shared.roots.write() {
+                    Ok(mut roots) => roots.remove(&dir.path),
+                    Err(_) => None,
+                };
+                if let Some(root) = removed {
+                    self.unwatch_paths(&root.watched);
+                }
+                return Err(state);
+            }
+        };
+
+        let entries: Arc<Vec<IndexedWorkspace>> = Arc::new(
+            discovery
+                .workspaces
+                .into_iter()
+                .map(|workspace| {
+                    let project = ProjectInfo::detect(&workspace.path);
+                    IndexedWorkspace::new(&workspace.path, workspace.name).with_project(project)
+                })
+                .collect(),
+        );
+
+        // 注意：注册监听时不能持有 roots 锁，否则可能与监听回调互相等待
+        self.watch_paths(&discovery.visited_dirs);
+
+        self.store(dir, entries.clone(), discovery.visited_dirs, generation);
+        Ok(entries)
+    }
+
+    /// 写入重建结果；`generation` 为开始重建时的变化次数，之后又有变化时结果仍为失效，下次查询重新遍历
+    ///
+    /// 重建期间目录已从配置中移除时丢弃结果；`watched` 的监听引用由索引接管，丢弃或被替换时释放。
+    fn store(
+        &self,
+        dir: &SearchDirectory,
+        entries: Arc<Vec<IndexedWorkspace>>,
+        watched: Vec<PathBuf>,
+        generation: u64,
+    ) {
+        let released = match self.shared.roots.write() {
+            Ok(mut roots) => {
+                // 持有 roots 锁检查变化次数，`prune` 无法在检查与写入之间移除该目录
+                let current = self
+                    .shared
+                    .generations
+                    .lock()
+                    .ok()
+                    .and_then(|generations| generations.get(&dir.path).copied());
+                match current {
+                    Some(current) => roots
+                        .insert(
+                            dir.path.clone(),
+                            RootIndex {
+                                config: dir.clone(),
+                                path: PathBuf::from(&dir.path),
+                                entries,
+                                watched,
+                                stale: current != generation,
+                            },
+                        )
+                        .map(|previous| previous.watched),
+                    None => Some(watched),
+                }
+            }
+            Err(_) => Some(watched),
+        };
+
+        if let Some(released) = released {
+            self.unwatch_paths(&released);
+        }
+    }
+
+    /// 移除不再配置的搜索目录及其下工作区的 Git 状态
+    pub(crate) fn prune(&self, directories: &[SearchDirectory]) {
+        self.shared.git.retain(|path| {
+            directories
+                .iter()
+                .any(|dir| Path::new(path).starts_with(&dir.path))
+        });
+
+        if let Ok(mut generations) = self.shared.generations.lock() {
+            generations.retain(|root, _| directories.iter().any(|dir| &dir.path == root));
+        }
+
+        let removed: Vec<PathBuf> = match self.shared.roots.write() {
+            Ok(mut roots) => {
+                let obsolete: Vec<String> = roots
+                    .keys()
+                    .filter(|key| !directories.iter().any(|dir| &dir.path == *key))
+                    .cloned()
+                    .collect();
+                obsolete
+                    .iter()
+                    .filter_map(|key| roots.remove(key))
+                    .flat_map(|root| root.watched)
+                    .collect()
+            }
+            Err(_) => return,
+        };
+
+        self.unwatch_paths(&removed);
+    }
+
+    /// 增加目录的监听引用，首次引用时开始监听（监听器不可用时静默降级）
+    ///
+    /// 每个遍历过的目录都以非递归方式监听，避免监听项目内部（如 `node_modules`）的海量变化。
+    fn watch_paths(&self, paths: &[PathBuf]) {
+        let Ok(mut state) = self.shared.watches.lock() else {
+            return;
+        };
+        let state = &mut *state;
+
+        if state.watcher.is_none() && !paths.is_empty() {
+            let weak: Weak<IndexShared> = Arc::downgrade(&self.shared);
+            let watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+                let Ok(event) = res else {
+                    return;
+                };
+                if !is_structural_change(&event.kind) {
+                    return;
+                }
+                if let Some(shared) = weak.upgrade() {
+                    for path in &event.paths {
+                        mark_stale(&shared, path);
+                    }
+                }
+            });
+            state.watcher = watcher.ok();
+        }
+
+        for path in paths {
+            let count = state.counts.entry(path.clone()).or_insert(0);
+            *count += 1;
+            if *count == 1 {
+                if let Some(watcher) = state.watcher.as_mut() {
+                    let _ = watcher.watch(path, RecursiveMode::NonRecursive);
+                }
+            }
+        }
+    }
+
+    /// 释放目录的监听引用，不再被引用的目录停止监听
+    fn unwatch_paths(&self, paths: &[PathBuf]) {
+        let Ok(mut state) = self.shared.watches.lock() else {
+            return;
+        };
+        let state = &mut *state;
+
+        for path in paths {
+            let Some(count) = state.counts.get_mut(path) else {
+                continue;
+            };
+            *count -= 1;
+            if *count == 0 {
+                state.counts.remove(path);
+                if let Some(watcher) = state.watcher.as_mut() {
+                    let _ = watcher.unwatch(path);
+                }
+            }
+        }
+    }
+}
+
+/// 只有目录的增删、改名才会影响索引，忽略文件内容修改等事件
+fn is_structural_change(kind: &EventKind) -> bool {
+    matches!(
+        kind,
+        EventKind::Any
+            | EventKind::Create(_)
+            | EventKind::Remove(_)
+            | EventKind::Modify(ModifyKind::Name(_))
+            | EventKind::Modify(ModifyKind::Any)
+    )
+}
+
+/// 将包含 `path` 的所有搜索目录标记为失效，并增加其变化次数（含正在重建、尚未写入索引的目录）
+fn mark_stale(shared: &IndexShared, path: &Path) {
+    let mut changed: Vec<String> = Vec::new();
+    if let Ok(mut roots) = shared.roots.write() {
+        for (key, root) in roots.iter_mut() {
+            if path.starts_with(&root.path) {
+                root.stale = true;
+                changed.push(key.clone());
+            }
+        }
+    }
+    if let Ok(pending) = shared.pending.lock() {
+        for key in pending.keys() {
+            if path.starts_with(key) && !changed.contains(key) {
+                changed.push(key.clone());
+            }
+        }
+    }
+    if let Ok(mut generations) = shared.generations.lock() {
+        for key in changed {
+            *generations.entry(key).or_insert(0) += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    fn names(snapshot: &[(String, RootEntries)]) -> Vec<String> {
+        let mut names: Vec<String> = snapshot
+            .iter()
+            .filter_map(|(_, entries)| entries.as_ref().ok())
+            .flat_map(|entries| entries.iter().map(|e| e.name.clone()))
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_snapshot_precomputes_match_forms() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("My-Project")).unwrap();
+        fs::write(temp_dir.path().join("file.txt"), "").unwrap();
+
+        let index = WorkspaceIndex::new();
+        let dir = SearchDirectory::new(temp_dir.path().to_string_lossy());
+        let snapshot = index.snapshot(std::slice::from_ref(&dir), TIMEOUT);
+
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(snapshot[0].0, dir.path);
+        let entries = snapshot[0].1.as_ref().unwrap();
+        let entry = &entries[0];
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entry.lower_chars.iter().collect::<String>(), "my-project");
+        assert_eq!(entry.pinyin_initials.len(), 1);
+        assert_eq!(entry.pinyin_initials[0].text, "myproject");
+    }
+
+    #[test]
+    fn test_snapshot_reuses_index_until_invalidated() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("first")).unwrap();
+
+        let index = WorkspaceIndex::new();
+        let dirs = vec![SearchDirectory::new(temp_dir.path().to_string_lossy())];
+        let before = index.snapshot(&dirs, TIMEOUT);
+        assert_eq!(names(&before), vec!["first"]);
+
+        // 失效后重建，新增的目录出现在结果中
+        fs::create_dir_all(temp_dir.path().join("second")).unwrap();
+        index.invalidate(&temp_dir.path().join("second"));
+        let after = index.snapshot(&dirs, TIMEOUT);
+        assert_eq!(names(&after), vec!["first", "second"]);
+    }
+
+    #[test]
+    fn test_change_during_rebuild_keeps_root_stale() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("first")).unwrap();
+
+        let index = WorkspaceIndex::new();
+        let dir = SearchDirectory::new(temp_dir.path().to_string_lossy());
+        let before = index.snapshot(std::slice::from_ref(&dir), TIMEOUT);
+        assert_eq!(names(&before), vec!["first"]);
+
+        // 模拟重建期间监听到新目录：写入旧的遍历结果后仍为失效，下次查询会重新遍历
+        let generation = index.shared.generations.lock().unwrap()[&dir.path];
+        fs::create_dir_all(temp_dir.path().join("second")).unwrap();
+        index.invalidate(&temp_dir.path().join("second"));
+        let entries = before[0].1.clone().unwrap();
+        index.store(&dir, entries, Vec::new(), generation);
+        assert!(index.shared.roots.read().unwrap()[&dir.path].stale);
+
+        let after = index.snapshot(std::slice::from_ref(&dir), TIMEOUT);
+        assert_eq!(names(&after), vec!["first", "second"]);
+    }
+
+    #[test]
+    fn test_prune_during_rebuild_drops_result() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("project")).unwrap();
+
+        let index = WorkspaceIndex::new();
+        let dir = SearchDirectory::new(temp_dir.path().to_string_lossy());
+        let before = index.snapshot(std::slice::from_ref(&dir), TIMEOUT);
+
+        // 模拟重建期间目录被移出配置：之后完成的重建不写回索引，也不保留监听
+        let generation = index.shared.generations.lock().unwrap()[&dir.path];
+        let watched = vec![temp_dir.path().join("project")];
+        index.watch_paths(&watched);
+        index.prune(&[]);
+        index.store(&dir, before[0].1.clone().unwrap(), watched, generation);
+        assert!(index.shared.roots.read().unwrap().is_empty());
+        assert!(index.shared.watches.lock().unwrap().counts.is_empty());
+    }
+
+    #[test]
+    fn test_prune_keeps_watches_shared_with_nested_root() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("org/repo")).unwrap();
+        let outer = SearchDirectory {
+            max_depth: 2,
+            ..SearchDirectory::new(temp_dir.path().to_string_lossy())
+        };
+        let inner = SearchDirectory::new(temp_dir.path().join("org").to_string_lossy());
+
+        let index = WorkspaceIndex::new();
+        index.snapshot(&[outer, inner.clone()], TIMEOUT);
+        let org = temp_dir.path().join("org");
+        assert_eq!(index.shared.watches.lock().unwrap().counts[&org], 2);
+
+        // 移除外层目录后，内层目录仍在监听两者共同遍历过的目录
+        index.snapshot(std::slice::from_ref(&inner), TIMEOUT);
+        let watches = index.shared.watches.lock().unwrap();
+        assert_eq!(watches.counts[&org], 1);
+        assert!(!watches.counts.contains_key(temp_dir.path()));
+    }
+
+    #[test]
+    fn test_snapshot_reports_missing_and_prunes_removed_roots() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("project")).unwrap();
+        let dir = SearchDirectory::new(temp_dir.path().to_string_lossy());
+
+        let index = WorkspaceIndex::new();
+        let snapshot = index.snapshot(&[dir, SearchDirectory::new("/nonexistent/path")], TIMEOUT);
+        assert_eq!(names(&snapshot), vec!["project"]);
+        assert_eq!(snapshot[1].1.as_ref().err(), Some(&RootState::Missing));
+        assert_eq!(index.shared.roots.read().unwrap().len(), 1);
+
+        // 搜索目录从配置中移除后不再返回
+        assert!(index.snapshot(&[], TIMEOUT).is_empty());
+        assert!(index.shared.roots.read().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_snapshot_rebuilds_when_options_change() {
+        // 同一目录修改遍历深度后应重新发现工作区
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("org/repo/.git")).unwrap();
+
+        let index = WorkspaceIndex::new();
+        let shallow = SearchDirectory::new(temp_dir.path().to_string_lossy());
+        assert_eq!(
+            names(&index.snapshot(std::slice::from_ref(&shallow), TIMEOUT)),
+            vec!["org"]
+        );
+
+        let deep = SearchDirectory {
+            max_depth: 2,
+            ..shallow
+        };
+        assert_eq!(names(&index.snapshot(&[deep], TIMEOUT)), vec!["repo"]);
+    }
+
+    #[test]
+    fn test_refresh_git_status_in_background() {
+        let temp_dir = TempDir::new().unwrap();
+        git2::Repository::init(temp_dir.path()).unwrap();
+        let path = temp_dir.path().to_string_lossy().to_string();
+        let index = WorkspaceIndex::new();
+        assert_eq!(index.git_status(&path), None);
+
+        // 读取在后台完成后推送一次，之后缓存中有状态
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let refresh = |sender: std::sync::mpsc::Sender<Vec<GitStatusUpdate>>| {
+            index.refresh_git_status([path.as_str()], move |updates| {
+                let _ = sender.send(updates);
+            })
+        };
+        refresh(sender.clone());
+        let updates = receiver.recv_timeout(TIMEOUT).unwrap();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].path, path);
+        assert!(updates[0].git.is_some());
+        assert_eq!(index.git_status(&path), updates[0].git);
+
+        // 有效期内不再读取
+        refresh(sender);
+        assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+    }
+}
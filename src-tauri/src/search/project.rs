@@ -0,0 +1,274 @@
+//! 项目类型识别
+//!
+//! 根据工作区根目录下的标记文件（`Cargo.toml`、`package.json` 及锁文件、`pom.xml` 等）识别项目类型与
+//! 构建工具，统计根目录和 `src` 下源文件的扩展名推断主要语言，并记录文件夹的修改时间。
+//! 结果在建立索引时计算并随索引缓存，搜索结果直接携带，供界面显示图标和按类型筛选。
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+/// 项目类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProjectType {
+    Rust,
+    Node,
+    Java,
+    Python,
+    Go,
+    DotNet,
+    Php,
+    Ruby,
+    Cpp,
+}
+
+/// 标记文件（`*.ext` 形式按扩展名匹配）、对应的项目类型与构建工具
+///
+/// 同一类型只取第一个命中的标记，因此锁文件排在 `package.json` 等通用标记之前。
+const PROJECT_MARKERS: &[(&str, ProjectType, &str)] = &[
+    ("Cargo.toml", ProjectType::Rust, "cargo"),
+    ("pnpm-lock.yaml", ProjectType::Node, "pnpm"),
+    ("yarn.lock", ProjectType::Node, "yarn"),
+    ("bun.lockb", ProjectType::Node, "bun"),
+    ("package.json", ProjectType::Node, "npm"),
+    ("pom.xml", ProjectType::Java, "maven"),
+    ("build.gradle", ProjectType::Java, "gradle"),
+    ("build.gradle.kts", ProjectType::Java, "gradle"),
+    ("poetry.lock", ProjectType::Python, "poetry"),
+    ("uv.lock", ProjectType::Python, "uv"),
+    ("Pipfile", ProjectType::Python, "pipenv"),
+    ("pyproject.toml", ProjectType::Python, "pip"),
+    ("setup.py", ProjectType::Python, "pip"),
+    ("requirements.txt", ProjectType::Python, "pip"),
+    ("go.mod", ProjectType::Go, "go"),
+    ("*.sln", ProjectType::DotNet, "dotnet"),
+    ("*.csproj", ProjectType::DotNet, "dotnet"),
+    ("*.fsproj", ProjectType::DotNet, "dotnet"),
+    ("composer.json", ProjectType::Php, "composer"),
+    ("Gemfile", ProjectType::Ruby, "bundler"),
+    ("CMakeLists.txt", ProjectType::Cpp, "cmake"),
+];
+
+/// 源文件扩展名与语言，统计数量相同时排在前面的优先
+const LANGUAGE_EXTENSIONS: &[(&str, &str)] = &[
+    ("rs", "Rust"),
+    ("ts", "TypeScript"),
+    ("tsx", "TypeScript"),
+    ("mts", "TypeScript"),
+    ("vue", "Vue"),
+    ("js", "JavaScript"),
+    ("jsx", "JavaScript"),
+    ("mjs", "JavaScript"),
+    ("cjs", "JavaScript"),
+    ("py", "Python"),
+    ("go", "Go"),
+    ("java", "Java"),
+    ("kt", "Kotlin"),
+    ("kts", "Kotlin"),
+    ("scala", "Scala"),
+    ("cs", "C#"),
+    ("fs", "F#"),
+    ("php", "PHP"),
+    ("rb", "Ruby"),
+    ("cpp", "C++"),
+    ("cc", "C++"),
+    ("cxx", "C++"),
+    ("hpp", "C++"),
+    ("c", "C"),
+    ("h", "C"),
+    ("swift", "Swift"),
+    ("dart", "Dart"),
+];
+
+/// 统计源文件时每个目录最多查看的条目数
+const MAX_SCANNED_ENTRIES: usize = 500;
+
+/// 工作区的项目信息
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectInfo {
+    /// 识别出的项目类型（如 Tauri 应用同时为 Node 与 Rust），未识别时为空
+    pub project_types: Vec<ProjectType>,
+    /// 与 `project_types` 一一对应的构建工具或包管理器（cargo、pnpm、maven…）
+    pub build_tools: Vec<&'static str>,
+    /// 主要语言
+    pub language: Option<&'static str>,
+    /// 文件夹的修改时间（Unix 毫秒）
+    pub modified: Option<u64>,
+}
+
+impl ProjectInfo {
+    /// 识别工作区的项目信息，目录不可读时返回空信息
+    pub(crate) fn detect(dir: &Path) -> Self {
+        let files = file_names(dir);
+        let mut info = Self {
+            modified: modified_millis(dir),
+            ..Self::default()
+        };
+
+        for (marker, project_type, tool) in PROJECT_MARKERS {
+            if info.project_types.contains(project_type) || !has_marker(&files, marker) {
+                continue;
+            }
+            info.project_types.push(*project_type);
+            info.build_tools.push(tool);
+        }
+
+        info.language = primary_language(&files, &file_names(&dir.join("src")))
+            .or_else(|| info.default_language(&files));
+        info
+    }
+
+    /// 是否属于任一给定类型，`types` 为空时不做筛选
+    pub(crate) fn matches_types(&self, types: &[ProjectType]) -> bool {
+        types.is_empty() || self.project_types.iter().any(|t| types.contains(t))
+    }
+
+    /// 没有源文件可供统计时，按项目类型推断语言
+    fn default_language(&self, files: &HashSet<String>) -> Option<&'static str> {
+        let language = match self.project_types.first()? {
+            ProjectType::Rust => "Rust",
+            ProjectType::Node if files.contains("tsconfig.json") => "TypeScript",
+            ProjectType::Node => "JavaScript",
+            ProjectType::Java if files.contains("build.gradle.kts") => "Kotlin",
+            ProjectType::Java => "Java",
+            ProjectType::Python => "Python",
+            ProjectType::Go => "Go",
+            ProjectType::DotNet => "C#",
+            ProjectType::Php => "PHP",
+            ProjectType::Ruby => "Ruby",
+            ProjectType::Cpp => "C++",
+        };
+        Some(language)
+    }
+}
+
+/// 列出目录下的文件名（最多 `MAX_SCANNED_ENTRIES` 个）
+fn file_names(dir: &Path) -> HashSet<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return HashSet::new();
+    };
+    entries
+        .flatten()
+        .take(MAX_SCANNED_ENTRIES)
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_file()))
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect()
+}
+
+fn has_marker(files: &HashSet<String>, marker: &str) -> bool {
+    match marker.strip_prefix('*') {
+        Some(suffix) => files.iter().any(|name| name.ends_with(suffix)),
+        None => files.contains(marker),
+    }
+}
+
+/// 按扩展名统计源文件最多的语言
+fn primary_language(
+    root_files: &HashSet<String>,
+    src_files: &HashSet<String>,
+) -> Option<&'static str> {
+    let mut counts: Vec<(&'static str, usize)> = Vec::new();
+    for name in root_files.iter().chain(src_files) {
+        let Some((_, extension)) = name.rsplit_once('.') else {
+            continue;
+        };
+        let Some((_, language)) = LANGUAGE_EXTENSIONS
+            .iter()
+            .find(|(ext, _)| ext.eq_ignore_ascii_case(extension))
+        else {
+            continue;
+        };
+        match counts.iter_mut().find(|(l, _)| l == language) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((language, 1)),
+        }
+    }
+
+    let rank = |language: &str| {
+        LANGUAGE_EXTENSIONS
+            .iter()
+            .position(|(_, l)| *l == language)
+            .unwrap_or(usize::MAX)
+    };
+    counts
+        .into_iter()
+        .max_by(|(a, count_a), (b, count_b)| count_a.cmp(count_b).then(rank(b).cmp(&rank(a))))
+        .map(|(language, _)| language)
+}
+
+fn modified_millis(dir: &Path) -> Option<u64> {
+    let modified = fs::metadata(dir).ok()?.modified().ok()?;
+    let millis = modified.duration_since(UNIX_EPOCH).ok()?.as_millis();
+    u64::try_from(millis).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn project(files: &[&str]) -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        for file in files {
+            let path = temp_dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        temp_dir
+    }
+
+    #[test]
+    fn test_detect_types_and_tools() {
+        // Tauri 应用：前端使用 pnpm，后端为 Cargo（位于根目录时）
+        let temp_dir = project(&[
+            "package.json",
+            "pnpm-lock.yaml",
+            "Cargo.toml",
+            "src/main.ts",
+            "src/App.vue",
+            "src/store.ts",
+        ]);
+        let info = ProjectInfo::detect(temp_dir.path());
+        assert_eq!(
+            info.project_types,
+            vec![ProjectType::Rust, ProjectType::Node]
+        );
+        assert_eq!(info.build_tools, vec!["cargo", "pnpm"]);
+        assert_eq!(info.language, Some("TypeScript"));
+        assert!(info.modified.is_some());
+    }
+
+    #[test]
+    fn test_detect_extension_markers_and_default_language() {
+        let temp_dir = project(&["App.sln"]);
+        let info = ProjectInfo::detect(temp_dir.path());
+        assert_eq!(info.project_types, vec![ProjectType::DotNet]);
+        assert_eq!(info.language, Some("C#"));
+
+        let temp_dir = project(&["build.gradle.kts"]);
+        let info = ProjectInfo::detect(temp_dir.path());
+        assert_eq!(info.build_tools, vec!["gradle"]);
+        assert_eq!(info.language, Some("Kotlin"));
+
+        // 普通文件夹没有项目信息
+        let temp_dir = project(&["notes.txt"]);
+        let info = ProjectInfo::detect(temp_dir.path());
+        assert!(info.project_types.is_empty());
+        assert_eq!(info.language, None);
+    }
+
+    #[test]
+    fn test_matches_types() {
+        let info = ProjectInfo {
+            project_types: vec![ProjectType::Go],
+            ..Default::default()
+        };
+        assert!(info.matches_types(&[]));
+        assert!(info.matches_types(&[ProjectType::Rust, ProjectType::Go]));
+        assert!(!info.matches_types(&[ProjectType::Node]));
+    }
+}
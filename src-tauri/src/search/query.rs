@@ -0,0 +1,252 @@
+//! 查询语法
+//!
+//! 在普通查询之上支持几个简单的操作符（以空白分隔，可任意组合）：
+//!
+//! - `@别名`：只搜索别名（或目录名）匹配的搜索目录，可出现多次
+//! - `-词`：排除名称包含该词的工作区
+//! - `"短语"`：名称必须原样包含该短语（忽略大小写，保留空格与分隔符）
+//! - `^前缀`、`后缀$`：名称必须以其开头/结尾，`^名称$` 要求名称完全一致
+//!
+//! 其余部分按原有的匹配器打分；只有操作符没有普通查询时，符合条件的工作区全部返回。
+
+use super::discovery::SearchDirectory;
+use super::index::IndexedWorkspace;
+use super::normalize;
+use super::scoring::lower_char;
+
+/// 解析后的查询
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ParsedQuery {
+    /// 交给匹配器打分的普通查询（各部分以空格连接）
+    pub text: String,
+    /// `@别名`
+    pub roots: Vec<String>,
+    /// `-词`
+    pub excludes: Vec<String>,
+    /// `"短语"`
+    pub phrases: Vec<String>,
+    /// `^前缀`
+    pub prefixes: Vec<String>,
+    /// `后缀$`
+    pub suffixes: Vec<String>,
+    /// `^名称$`
+    pub exacts: Vec<String>,
+}
+
+impl ParsedQuery {
+    pub fn parse(input: &str) -> Self {
+        let mut parsed = Self::default();
+        let mut text: Vec<&str> = Vec::new();
+        let mut rest = input.trim_start();
+
+        while !rest.is_empty() {
+            if let Some(quoted) = rest.strip_prefix('"') {
+                // 未闭合的引号一直延续到查询末尾
+                let (phrase, remaining) = quoted.split_once('"').unwrap_or((quoted, ""));
+                if !phrase.is_empty() {
+                    parsed.phrases.push(lowercase(phrase));
+                }
+                rest = remaining.trim_start();
+                continue;
+            }
+
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            let token = &rest[..end];
+            rest = rest[end..].trim_start();
+
+            // 单独的操作符字符按普通文本处理
+            if let Some(alias) = token.strip_prefix('@').filter(|s| !s.is_empty()) {
+                parsed.roots.push(alias.to_string());
+            } else if let Some(term) = token.strip_prefix('-').filter(|s| !s.is_empty()) {
+                parsed.excludes.push(lowercase(term));
+            } else if !parsed.push_anchor(token) {
+                text.push(token);
+            }
+        }
+
+        parsed.text = text.join(" ");
+        parsed
+    }
+
+    /// 解析 `^前缀`、`后缀$` 与 `^名称$`，同一个词两端的锚点一起去掉；不是锚点时返回 false
+    fn push_anchor(&mut self, token: &str) -> bool {
+        let (start, rest) = match token.strip_prefix('^') {
+            Some(rest) => (true, rest),
+            None => (false, token),
+        };
+        let (end, term) = match rest.strip_suffix('$') {
+            Some(term) => (true, term),
+            None => (false, rest),
+        };
+        if term.is_empty() {
+            return false;
+        }
+        let term = lowercase(term);
+        match (start, end) {
+            (true, true) => self.exacts.push(term),
+            (true, false) => self.prefixes.push(term),
+            (false, true) => self.suffixes.push(term),
+            (false, false) => return false,
+        }
+        true
+    }
+
+    /// 搜索目录是否在 `@别名` 限定的范围内
+    pub fn includes_root(&self, directory: &SearchDirectory) -> bool {
+        self.roots.is_empty()
+            || self
+                .roots
+                .iter()
+                .any(|alias| directory.matches_alias(alias))
+    }
+
+    /// 检查名称是否满足排除、短语与锚点条件，满足时返回短语与锚点命中的字符下标
+    pub fn filter(&self, entry: &IndexedWorkspace) -> Option<Vec<usize>> {
+        let name = &entry.lower_chars;
+        let excluded = self.excludes.iter().any(|term| {
+            let norm = normalize(term);
+            contains(name, term) || (!norm.is_empty() && entry.name_norm.contains(&norm))
+        });
+        if excluded {
+            return None;
+        }
+
+        let mut positions = Vec::new();
+        for phrase in &self.phrases {
+            let needle = chars(phrase);
+            let start = find(name, &needle)?;
+            positions.extend(start..start + needle.len());
+        }
+        for prefix in &self.prefixes {
+            let needle = chars(prefix);
+            if !name.starts_with(&needle) {
+                return None;
+            }
+            positions.extend(0..needle.len());
+        }
+        for exact in &self.exacts {
+            if *name != chars(exact) {
+                return None;
+            }
+            positions.extend(0..name.len());
+        }
+        for suffix in &self.suffixes {
+            let needle = chars(suffix);
+            if !name.ends_with(&needle) {
+                return None;
+            }
+            positions.extend(name.len() - needle.len()..name.len());
+        }
+        Some(positions)
+    }
+}
+
+/// 与索引中 `lower_chars` 一致的逐字符小写
+fn chars(text: &str) -> Vec<char> {
+    text.chars().map(lower_char).collect()
+}
+
+/// 查询词按与名称相同的方式逐字符小写
+fn lowercase(text: &str) -> String {
+    text.chars().map(lower_char).collect()
+}
+
+fn contains(haystack: &[char], term: &str) -> bool {
+    find(haystack, &chars(term)).is_some()
+}
+
+fn find(haystack: &[char], needle: &[char]) -> Option<usize> {
+    if needle.len() > haystack.len() {
+        return None;
+    }
+    (0..=haystack.len() - needle.len()).find(|&start| haystack[start..].starts_with(needle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn entry(name: &str) -> IndexedWorkspace {
+        IndexedWorkspace::new(Path::new("/tmp").join(name).as_path(), name.to_string())
+    }
+
+    #[test]
+    fn test_parse_operators() {
+        let parsed = ParsedQuery::parse(r#"@work api -legacy "user service" ^svc rs$ gw"#);
+        assert_eq!(parsed.text, "api gw");
+        assert_eq!(parsed.roots, vec!["work"]);
+        assert_eq!(parsed.excludes, vec!["legacy"]);
+        assert_eq!(parsed.phrases, vec!["user service"]);
+        assert_eq!(parsed.prefixes, vec!["svc"]);
+        assert_eq!(parsed.suffixes, vec!["rs"]);
+    }
+
+    #[test]
+    fn test_parse_plain_and_lone_operators() {
+        assert_eq!(ParsedQuery::parse("项目 管理").text, "项目 管理");
+        let parsed = ParsedQuery::parse("- ^ $ @");
+        assert_eq!(parsed.text, "- ^ $ @");
+        assert_eq!(parsed.roots.len() + parsed.excludes.len(), 0);
+        // 未闭合的引号
+        assert_eq!(ParsedQuery::parse("\"my app").phrases, vec!["my app"]);
+    }
+
+    #[test]
+    fn test_filter_excludes_and_anchors() {
+        let parsed = ParsedQuery::parse("-legacy ^api");
+        assert_eq!(parsed.filter(&entry("API-Gateway")), Some(vec![0, 1, 2]));
+        assert!(parsed.filter(&entry("api-legacy")).is_none());
+        assert!(parsed.filter(&entry("my-api")).is_none());
+
+        let parsed = ParsedQuery::parse("-old tools$");
+        assert_eq!(parsed.filter(&entry("ytools")), Some(vec![1, 2, 3, 4, 5]));
+        // 排除词同样忽略分隔符
+        assert!(parsed.filter(&entry("O-L-D tools")).is_none());
+    }
+
+    #[test]
+    fn test_filter_exact_anchor() {
+        let parsed = ParsedQuery::parse("^api$");
+        assert_eq!(parsed.exacts, vec!["api"]);
+        assert!(parsed.prefixes.is_empty() && parsed.suffixes.is_empty());
+        assert_eq!(parsed.filter(&entry("API")), Some(vec![0, 1, 2]));
+        assert!(parsed.filter(&entry("api-api")).is_none());
+        assert!(parsed.filter(&entry("my-api")).is_none());
+        // 只有锚点的词按普通文本处理
+        assert_eq!(ParsedQuery::parse("^$").text, "^$");
+    }
+
+    #[test]
+    fn test_terms_lowercase_like_names() {
+        // İ 整体小写为两个字符，逐字符小写与名称一致
+        let parsed = ParsedQuery::parse("\"İstanbul\" -İzmir");
+        assert_eq!(parsed.phrases, vec!["istanbul"]);
+        assert_eq!(parsed.excludes, vec!["izmir"]);
+        assert!(parsed.filter(&entry("İstanbul App")).is_some());
+        assert!(parsed.filter(&entry("İzmir İstanbul")).is_none());
+    }
+
+    #[test]
+    fn test_filter_exact_phrase() {
+        let parsed = ParsedQuery::parse("\"my app\"");
+        assert_eq!(
+            parsed.filter(&entry("My App v2")),
+            Some(vec![0, 1, 2, 3, 4, 5])
+        );
+        assert!(parsed.filter(&entry("my-app")).is_none());
+    }
+
+    #[test]
+    fn test_includes_root_by_alias_or_name() {
+        let parsed = ParsedQuery::parse("@Work");
+        let aliased = SearchDirectory {
+            alias: Some("work".to_string()),
+            ..SearchDirectory::new("/home/me/clients")
+        };
+        assert!(parsed.includes_root(&aliased));
+        assert!(parsed.includes_root(&SearchDirectory::new("/home/me/work")));
+        assert!(!parsed.includes_root(&SearchDirectory::new("/home/me/personal")));
+        assert!(ParsedQuery::parse("api").includes_root(&SearchDirectory::new("/x")));
+    }
+}
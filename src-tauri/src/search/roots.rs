@@ -0,0 +1,212 @@
+//! 搜索目录状态
+//!
+//! 搜索目录可能已被删除、没有读取权限，或位于长时间无响应的网络挂载上。每个搜索目录的遍历
+//! 都在独立线程中进行并设置超时，超时的目录本次跳过而不阻塞整个搜索；各目录的状态与条目数
+//! 随搜索结果一起返回，供设置页标记异常的搜索目录。
+
+use super::tasks::{CancelToken, POLL_INTERVAL};
+use serde::Serialize;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+/// 默认的单个搜索目录遍历超时（毫秒）
+pub const DEFAULT_ROOT_TIMEOUT_MS: u64 = 3_000;
+
+/// 搜索目录的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RootState {
+    /// 遍历成功
+    Ok,
+    /// 目录不存在（或不是目录）
+    Missing,
+    /// 没有读取权限
+    PermissionDenied,
+    /// 遍历超时
+    TimedOut,
+}
+
+/// 单个搜索目录的遍历结果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RootStatus {
+    /// 搜索目录（与配置中的写法一致）
+    pub root: String,
+    pub state: RootState,
+    /// 目录下的条目数（工作区搜索为工作区数，文件搜索为遍历到的文件数）
+    pub entries: usize,
+}
+
+impl RootStatus {
+    /// 由遍历结果构造状态，失败时条目数为 0
+    pub(crate) fn from_result<T>(
+        root: &str,
+        result: &Result<T, RootState>,
+        len: impl Fn(&T) -> usize,
+    ) -> Self {
+        let (state, entries) = match result {
+            Ok(value) => (RootState::Ok, len(value)),
+            Err(state) => (*state, 0),
+        };
+        Self {
+            root: root.to_string(),
+            state,
+            entries,
+        }
+    }
+}
+
+/// 检查搜索目录是否存在且可读
+pub(crate) fn probe_root(path: &Path) -> Result<(), RootState> {
+    match fs::read_dir(path) {
+        Ok(_) => Ok(()),
+        Err(error) if error.kind() == ErrorKind::PermissionDenied => {
+            Err(RootState::PermissionDenied)
+        }
+        Err(_) => Err(RootState::Missing),
+    }
+}
+
+/// 在后台线程中执行 `task`，超过 `timeout` 仍未完成时返回 `TimedOut`
+///
+/// 等待期间请求被取消时同样立即返回 `TimedOut`，调用方根据取消标记丢弃结果。
+/// 超时后线程不会被强制结束（阻塞在文件系统调用上的线程无法中断），其结果会被丢弃。
+pub(crate) fn with_timeout<T: Send + 'static>(
+    timeout: Duration,
+    cancel: &CancelToken,
+    task: impl FnOnce() -> Result<T, RootState> + Send + 'static,
+) -> Result<T, RootState> {
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = sender.send(task());
+    });
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        let wait = deadline
+            .saturating_duration_since(Instant::now())
+            .min(POLL_INTERVAL);
+        match receiver.recv_timeout(wait) {
+            Ok(result) => return result,
+            Err(RecvTimeoutError::Timeout)
+                if !cancel.is_cancelled() && Instant::now() < deadline => {}
+            Err(_) => return Err(RootState::TimedOut),
+        }
+    }
+}
+
+/// 后台遍历的结果，同一目录的多次查询等待同一次遍历
+pub(crate) struct PendingResult<T> {
+    /// 遍历结果，完成前为 None
+    result: Mutex<Option<T>>,
+    done: Condvar,
+}
+
+impl<T> Default for PendingResult<T> {
+    fn default() -> Self {
+        Self {
+            result: Mutex::new(None),
+            done: Condvar::new(),
+        }
+    }
+}
+
+impl<T: Clone> PendingResult<T> {
+    /// 写入结果并唤醒所有等待者
+    pub fn finish(&self, value: T) {
+        if let Ok(mut result) = self.result.lock() {
+            *result = Some(value);
+        }
+        self.done.notify_all();
+    }
+
+    /// 等待结果，超过 `timeout` 或等待期间请求被取消时返回 None
+    pub fn wait(&self, timeout: Duration, cancel: &CancelToken) -> Option<T> {
+        let mut result = self.result.lock().ok()?;
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(value) = result.as_ref() {
+                return Some(value.clone());
+            }
+            let wait = deadline.saturating_duration_since(Instant::now());
+            if wait.is_zero() || cancel.is_cancelled() {
+                return None;
+            }
+            result = self
+                .done
+                .wait_timeout(result, wait.min(POLL_INTERVAL))
+                .ok()?
+                .0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_probe_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("file.txt");
+        fs::write(&file, "").unwrap();
+
+        assert_eq!(probe_root(temp_dir.path()), Ok(()));
+        assert_eq!(
+            probe_root(Path::new("/nonexistent/path")),
+            Err(RootState::Missing)
+        );
+        // 文件不能作为搜索目录
+        assert_eq!(probe_root(&file), Err(RootState::Missing));
+    }
+
+    #[test]
+    fn test_with_timeout() {
+        let cancel = CancelToken::default();
+        let quick = with_timeout(Duration::from_secs(5), &cancel, || Ok(1));
+        assert_eq!(quick, Ok(1));
+
+        // 模拟无响应的网络挂载
+        let hung = with_timeout(Duration::from_millis(20), &cancel, || {
+            std::thread::sleep(Duration::from_secs(2));
+            Ok(1)
+        });
+        assert_eq!(hung, Err(RootState::TimedOut));
+    }
+
+    #[test]
+    fn test_pending_result_wait() {
+        let cancel = CancelToken::default();
+        let pending = std::sync::Arc::new(PendingResult::default());
+        assert_eq!(pending.wait(Duration::from_millis(20), &cancel), None);
+
+        let writer = pending.clone();
+        std::thread::spawn(move || writer.finish(7));
+        assert_eq!(pending.wait(Duration::from_secs(5), &cancel), Some(7));
+
+        // 已取消的请求不再等待
+        let unfinished = PendingResult::<i32>::default();
+        cancel.cancel();
+        let started = Instant::now();
+        assert_eq!(unfinished.wait(Duration::from_secs(30), &cancel), None);
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_with_timeout_returns_when_cancelled() {
+        let cancel = CancelToken::default();
+        cancel.cancel();
+        let started = Instant::now();
+        let hung = with_timeout(Duration::from_secs(30), &cancel, || {
+            std::thread::sleep(Duration::from_secs(2));
+            Ok(1)
+        });
+        assert_eq!(hung, Err(RootState::TimedOut));
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+}
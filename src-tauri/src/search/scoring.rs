@@ -0,0 +1,659 @@
+//! 搜索评分
+//!
+//! 对每个候选工作区运行所有匹配器（原文、规范化、拼音全拼、拼音首字母、模糊），取得分最高的一种作为结果。
+//! 匹配方式决定基础分，再叠加完全匹配、前缀、词首、连续匹配等加分，并扣除未匹配长度、编辑距离等，
+//! 所有候选使用同一把尺子排序，保证最佳匹配总在最前。
+
+use super::fuzzy_pinyin::FuzzyPinyinRules;
+use super::index::IndexedWorkspace;
+use super::subsequence::match_subsequence;
+use super::syllables::{match_syllables, SyllableMode};
+use super::transliterate::to_simplified;
+use super::{normalize, MatchKind, MatchRange};
+
+/// 原文包含匹配的基础分
+const LITERAL_WEIGHT: i64 = 1000;
+/// 去除分隔符后包含匹配的基础分
+const NORMALIZED_WEIGHT: i64 = 850;
+/// 拼音全拼匹配的基础分
+const PINYIN_FULL_WEIGHT: i64 = 700;
+/// 拼音首字母匹配的基础分
+const PINYIN_INITIALS_WEIGHT: i64 = 600;
+/// 拼音音节混合（全拼、部分音节、首字母混用）匹配的基础分
+const PINYIN_MIXED_WEIGHT: i64 = 550;
+/// 子序列匹配的基础分
+const SUBSEQUENCE_WEIGHT: i64 = 400;
+/// 子序列对齐得分的上限，保证其不会越过拼音首字母匹配
+const SUBSEQUENCE_SCORE_CAP: i64 = 150;
+/// 模糊匹配的基础分
+const FUZZY_WEIGHT: i64 = 200;
+
+/// 名称与查询完全相同
+const EXACT_BONUS: i64 = 300;
+/// 从名称开头开始匹配
+const PREFIX_BONUS: i64 = 150;
+/// 从单词开头（分隔符之后、驼峰大写、字母数字交界）开始匹配
+const BOUNDARY_BONUS: i64 = 80;
+/// 每个连续匹配的字符
+const CONTIGUOUS_BONUS: i64 = 8;
+/// 名称中每个未被匹配的字符扣分（同等条件下偏好更短、更贴切的名称）
+const UNMATCHED_PENALTY: i64 = 1;
+/// 未匹配字符扣分上限，避免长名称被过度压制
+const UNMATCHED_PENALTY_CAP: i64 = 60;
+/// 每个使用模糊拼音拼写命中的字符扣分
+const FUZZY_PINYIN_PENALTY: i64 = 20;
+/// 模糊匹配中每次编辑操作的扣分
+const EDIT_PENALTY: i64 = 30;
+
+/// 预处理后的查询
+pub(crate) struct PreparedQuery {
+    /// 小写查询
+    lower: String,
+    /// 小写查询的字符序列
+    lower_chars: Vec<char>,
+    /// 规范化后的查询
+    norm: String,
+    /// 去除分隔符后的小写字符序列（保留汉字，繁体转为简体），用于音节混合匹配
+    compact_chars: Vec<char>,
+    /// 开启的模糊拼音规则掩码
+    fuzzy_pinyin: u8,
+}
+
+impl PreparedQuery {
+    pub fn new(query: &str) -> Self {
+        let lower_chars: Vec<char> = query.chars().map(lower_char).collect();
+        let lower: String = lower_chars.iter().collect();
+        Self {
+            norm: normalize(&lower),
+            compact_chars: lower_chars
+                .iter()
+                .copied()
+                .filter(|c| c.is_alphanumeric())
+                .map(to_simplified)
+                .collect(),
+            lower,
+            lower_chars,
+            fuzzy_pinyin: 0,
+        }
+    }
+
+    /// 启用模糊拼音规则
+    pub fn with_fuzzy_pinyin(mut self, rules: &FuzzyPinyinRules) -> Self {
+        self.fuzzy_pinyin = rules.mask();
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lower.is_empty()
+    }
+}
+
+/// 单个候选的最佳匹配
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ScoredMatch {
+    pub kind: MatchKind,
+    pub score: i64,
+    /// 原名称中命中的字符区间
+    pub highlights: Vec<MatchRange>,
+}
+
+/// 某一匹配器的得分及命中的原名称字符下标
+struct Candidate {
+    score: i64,
+    positions: Vec<usize>,
+}
+
+/// 计算候选工作区与查询的最佳匹配，不匹配时返回 None
+pub(crate) fn score_entry(query: &PreparedQuery, entry: &IndexedWorkspace) -> Option<ScoredMatch> {
+    let mut best: Option<(MatchKind, Candidate)> = None;
+    consider(&mut best, MatchKind::Literal, literal_score(query, entry));
+    consider(
+        &mut best,
+        MatchKind::Normalized,
+        normalized_score(query, entry),
+    );
+    consider(
+        &mut best,
+        MatchKind::PinyinFull,
+        syllable_score(
+            &query.lower_chars,
+            query.fuzzy_pinyin,
+            entry,
+            SyllableMode::Full,
+            PINYIN_FULL_WEIGHT,
+        ),
+    );
+    consider(
+        &mut best,
+        MatchKind::PinyinInitials,
+        syllable_score(
+            &query.lower_chars,
+            query.fuzzy_pinyin,
+            entry,
+            SyllableMode::Initials,
+            PINYIN_INITIALS_WEIGHT,
+        ),
+    );
+
+    if best.is_none() {
+        consider(
+            &mut best,
+            MatchKind::PinyinMixed,
+            syllable_score(
+                &query.compact_chars,
+                query.fuzzy_pinyin,
+                entry,
+                SyllableMode::Mixed,
+                PINYIN_MIXED_WEIGHT,
+            ),
+        );
+    }
+
+    // 包含类匹配的基础分总是高于子序列与模糊匹配，已有结果时无需再计算代价较高的对齐得分
+    if best.is_none() {
+        consider(
+            &mut best,
+            MatchKind::Subsequence,
+            subsequence_score(query, entry),
+        );
+    }
+    if best.is_none() {
+        consider(&mut best, MatchKind::Fuzzy, fuzzy_score(query, entry));
+    }
+    best.map(|(kind, candidate)| ScoredMatch {
+        kind,
+        score: candidate.score,
+        highlights: merge_ranges(candidate.positions),
+    })
+}
+
+/// 得分更高时替换当前最佳匹配
+fn consider(
+    best: &mut Option<(MatchKind, Candidate)>,
+    kind: MatchKind,
+    candidate: Option<Candidate>,
+) {
+    if let Some(candidate) = candidate {
+        if best.as_ref().is_none_or(|(_, b)| candidate.score > b.score) {
+            *best = Some((kind, candidate));
+        }
+    }
+}
+
+/// 原文包含匹配：在所有出现位置中取加分最高的一处
+fn literal_score(query: &PreparedQuery, entry: &IndexedWorkspace) -> Option<Candidate> {
+    let needle = &query.lower_chars;
+    let haystack = &entry.lower_chars;
+    let (position_bonus, start) = find_all(haystack, needle)
+        .map(|start| {
+            let bonus = if start == 0 {
+                PREFIX_BONUS
+            } else if entry.word_starts[start] {
+                BOUNDARY_BONUS
+            } else {
+                0
+            };
+            // 同等加分时取最靠前的位置
+            (bonus, std::cmp::Reverse(start))
+        })
+        .max()
+        .map(|(bonus, start)| (bonus, start.0))?;
+
+    Some(Candidate {
+        score: LITERAL_WEIGHT
+            + position_bonus
+            + contiguous_bonus(needle.len())
+            + exact_bonus(haystack.len(), needle.len())
+            - unmatched_penalty(haystack.len(), needle.len()),
+        positions: (start..start + needle.len()).collect(),
+    })
+}
+
+/// 规范化包含匹配（忽略分隔符与大小写）
+fn normalized_score(query: &PreparedQuery, entry: &IndexedWorkspace) -> Option<Candidate> {
+    if query.norm.is_empty() {
+        return None;
+    }
+    let byte_start = entry.name_norm.find(&query.norm)?;
+    let start = entry.name_norm[..byte_start].chars().count();
+    let name_len = entry.norm_sources.len();
+    let query_len = query.norm.chars().count();
+
+    Some(Candidate {
+        score: NORMALIZED_WEIGHT
+            + if start == 0 { PREFIX_BONUS } else { 0 }
+            + contiguous_bonus(query_len)
+            + exact_bonus(name_len, query_len)
+            - unmatched_penalty(name_len, query_len),
+        positions: entry.norm_sources[start..start + query_len].to_vec(),
+    })
+}
+
+/// 拼音读音网格匹配（全拼、首字母或混合输入），多音字的任一读音均可
+fn syllable_score(
+    query: &[char],
+    fuzzy_pinyin: u8,
+    entry: &IndexedWorkspace,
+    mode: SyllableMode,
+    weight: i64,
+) -> Option<Candidate> {
+    let matched = match_syllables(&entry.readings, query, mode, fuzzy_pinyin)?;
+    let matched_len = matched.positions.len();
+    Some(Candidate {
+        score: weight
+            + if matched.prefix { PREFIX_BONUS } else { 0 }
+            + contiguous_bonus(query.len())
+            + if matched.complete { EXACT_BONUS } else { 0 }
+            - unmatched_penalty(matched.significant, matched_len)
+            - matched.fuzzy as i64 * FUZZY_PINYIN_PENALTY,
+        // 命中位置已映射回产生拼音的中文字符
+        positions: matched.positions,
+    })
+}
+
+/// 子序列匹配：查询（忽略分隔符）的字符按顺序出现在名称中
+fn subsequence_score(query: &PreparedQuery, entry: &IndexedWorkspace) -> Option<Candidate> {
+    let query_chars: Vec<char> = query.norm.chars().collect();
+    let matched = match_subsequence(&entry.lower_chars, &entry.word_starts, &query_chars)?;
+    Some(Candidate {
+        score: SUBSEQUENCE_WEIGHT + matched.score.clamp(0, SUBSEQUENCE_SCORE_CAP),
+        positions: matched.positions,
+    })
+}
+
+/// 模糊匹配：查询是名称（或其拼音）的子序列，或编辑距离在容错范围内
+///
+/// 拼音形式为有上限的读音组合，极端的多音字名称只会用常用读音参与模糊匹配。
+fn fuzzy_score(query: &PreparedQuery, entry: &IndexedWorkspace) -> Option<Candidate> {
+    let mut forms: Vec<(&str, &[usize], &str)> = Vec::new();
+    if !query.norm.is_empty() {
+        forms.push((&entry.name_norm, &entry.norm_sources, &query.norm));
+    }
+    for variant in entry.pinyin_full.iter().chain(&entry.pinyin_initials) {
+        forms.push((&variant.text, &variant.sources, &query.lower));
+    }
+
+    forms
+        .into_iter()
+        .filter_map(|(form, sources, query)| {
+            let query_len = query.chars().count();
+            let form_len = form.chars().count();
+            let lcs_sub = longest_common_substring_len(form, query);
+            let lcs_seq = longest_common_subsequence_len(form, query);
+
+            let score = if lcs_seq == query_len {
+                // 查询的所有字符按顺序出现在名称中
+                FUZZY_WEIGHT + contiguous_bonus(lcs_sub) - unmatched_penalty(form_len, query_len)
+            } else {
+                let edit = levenshtein_distance(form, query);
+                if edit > max_typos(query_len) {
+                    return None;
+                }
+                FUZZY_WEIGHT + contiguous_bonus(lcs_sub) - edit as i64 * EDIT_PENALTY
+            };
+
+            Some(Candidate {
+                score,
+                positions: common_subsequence_positions(form, query)
+                    .into_iter()
+                    .map(|i| sources[i])
+                    .collect(),
+            })
+        })
+        .max_by_key(|candidate| candidate.score)
+}
+
+/// 允许的拼写错误数：每 4 个字符容许 1 处，至少 1 处
+fn max_typos(query_len: usize) -> usize {
+    (query_len / 4).max(1)
+}
+
+fn contiguous_bonus(len: usize) -> i64 {
+    len as i64 * CONTIGUOUS_BONUS
+}
+
+fn exact_bonus(name_len: usize, query_len: usize) -> i64 {
+    if name_len == query_len {
+        EXACT_BONUS
+    } else {
+        0
+    }
+}
+
+fn unmatched_penalty(name_len: usize, query_len: usize) -> i64 {
+    (name_len.saturating_sub(query_len) as i64 * UNMATCHED_PENALTY).min(UNMATCHED_PENALTY_CAP)
+}
+
+/// 查找 `needle` 在 `haystack` 中的所有起始位置（按字符计）
+fn find_all<'a>(haystack: &'a [char], needle: &'a [char]) -> impl Iterator<Item = usize> + 'a {
+    let last_start = if needle.is_empty() || needle.len() > haystack.len() {
+        0
+    } else {
+        haystack.len() - needle.len() + 1
+    };
+    (0..last_start).filter(move |&start| haystack[start..start + needle.len()] == *needle)
+}
+
+/// 将命中的字符下标合并为有序、不重叠的连续区间
+pub(crate) fn merge_ranges(mut positions: Vec<usize>) -> Vec<MatchRange> {
+    positions.sort_unstable();
+    positions.dedup();
+
+    let mut ranges: Vec<MatchRange> = Vec::new();
+    for position in positions {
+        match ranges.last_mut() {
+            Some(last) if last.end == position => last.end += 1,
+            _ => ranges.push(MatchRange {
+                start: position,
+                end: position + 1,
+            }),
+        }
+    }
+    ranges
+}
+
+/// 逐字符转小写（只取第一个字符，保证与原名称按字符一一对应）
+pub(crate) fn lower_char(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// 计算每个字符是否为单词开头：名称开头、分隔符之后、驼峰大写、字母与数字交界、ASCII 与非 ASCII 交界
+pub(crate) fn word_starts(chars: &[char]) -> Vec<bool> {
+    chars
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| {
+            if i == 0 {
+                return true;
+            }
+            let prev = chars[i - 1];
+            if !c.is_alphanumeric() {
+                return false;
+            }
+            !prev.is_alphanumeric()
+                || (prev.is_lowercase() && c.is_uppercase())
+                || (prev.is_numeric() != c.is_numeric())
+                || (prev.is_ascii() != c.is_ascii())
+        })
+        .collect()
+}
+
+/// 最长公共子串长度（连续匹配长度）
+fn longest_common_substring_len(a: &str, b: &str) -> usize {
+    if a.is_empty() || b.is_empty() {
+        return 0;
+    }
+    let ac: Vec<char> = a.chars().collect();
+    let bc: Vec<char> = b.chars().collect();
+    let mut dp = vec![0usize; bc.len() + 1];
+    let mut best = 0usize;
+    for i in 1..=ac.len() {
+        let mut prev = 0usize;
+        for j in 1..=bc.len() {
+            let tmp = dp[j];
+            if ac[i - 1] == bc[j - 1] {
+                dp[j] = prev + 1;
+                if dp[j] > best {
+                    best = dp[j];
+                }
+            } else {
+                dp[j] = 0;
+            }
+            prev = tmp;
+        }
+    }
+    best
+}
+
+/// 最长公共子序列长度（非连续匹配总量）
+fn longest_common_subsequence_len(a: &str, b: &str) -> usize {
+    if a.is_empty() || b.is_empty() {
+        return 0;
+    }
+    let ac: Vec<char> = a.chars().collect();
+    let bc: Vec<char> = b.chars().collect();
+    let mut prev = vec![0usize; bc.len() + 1];
+    let mut curr = vec![0usize; bc.len() + 1];
+    for i in 1..=ac.len() {
+        for j in 1..=bc.len() {
+            curr[j] = if ac[i - 1] == bc[j - 1] {
+                prev[j - 1] + 1
+            } else {
+                prev[j].max(curr[j - 1])
+            };
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[bc.len()]
+}
+
+/// 最长公共子序列在 `a` 中对应的字符下标（回溯 LCS 表得到）
+fn common_subsequence_positions(a: &str, b: &str) -> Vec<usize> {
+    let ac: Vec<char> = a.chars().collect();
+    let bc: Vec<char> = b.chars().collect();
+    let (n, m) = (ac.len(), bc.len());
+    let mut dp = vec![vec![0usize; m + 1]; n + 1];
+    for i in 1..=n {
+        for j in 1..=m {
+            dp[i][j] = if ac[i - 1] == bc[j - 1] {
+                dp[i - 1][j - 1] + 1
+            } else {
+                dp[i - 1][j].max(dp[i][j - 1])
+            };
+        }
+    }
+
+    let mut positions = Vec::new();
+    let (mut i, mut j) = (n, m);
+    while i > 0 && j > 0 {
+        if ac[i - 1] == bc[j - 1] {
+            positions.push(i - 1);
+            i -= 1;
+            j -= 1;
+        } else if dp[i - 1][j] >= dp[i][j - 1] {
+            i -= 1;
+        } else {
+            j -= 1;
+        }
+    }
+    positions.reverse();
+    positions
+}
+
+/// Levenshtein 编辑距离（大小写与分隔符已通过 normalize 处理在外部）
+fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let ac: Vec<char> = a.chars().collect();
+    let bc: Vec<char> = b.chars().collect();
+    let n = ac.len();
+    let m = bc.len();
+    if n == 0 {
+        return m;
+    }
+    if m == 0 {
+        return n;
+    }
+
+    let mut prev: Vec<usize> = (0..=m).collect();
+    let mut curr: Vec<usize> = vec![0; m + 1];
+    for i in 1..=n {
+        curr[0] = i;
+        for j in 1..=m {
+            let cost = if ac[i - 1] == bc[j - 1] { 0 } else { 1 };
+            curr[j] = (prev[j] + 1) // 删除
+                .min(curr[j - 1] + 1) // 插入
+                .min(prev[j - 1] + cost); // 替换
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[m]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn score(query: &str, name: &str) -> Option<ScoredMatch> {
+        let entry = IndexedWorkspace::new(&Path::new("/tmp").join(name), name.to_string());
+        score_entry(&PreparedQuery::new(query), &entry)
+    }
+
+    #[test]
+    fn test_word_starts() {
+        let chars: Vec<char> = "myApp-v2 工具".chars().collect();
+        let starts: Vec<usize> = word_starts(&chars)
+            .iter()
+            .enumerate()
+            .filter(|(_, &s)| s)
+            .map(|(i, _)| i)
+            .collect();
+        // m, A, v, 2, 工
+        assert_eq!(starts, vec![0, 2, 6, 7, 9]);
+    }
+
+    #[test]
+    fn test_exact_beats_prefix_beats_boundary_beats_middle() {
+        let exact = score("api", "api").unwrap().score;
+        let prefix = score("api", "api-server").unwrap().score;
+        let boundary = score("api", "web-api-gw").unwrap().score;
+        let middle = score("api", "rapidjson").unwrap().score;
+        assert!(exact > prefix);
+        assert!(prefix > boundary);
+        assert!(boundary > middle);
+    }
+
+    #[test]
+    fn test_match_kinds_are_ordered_by_weight() {
+        let literal = score("xm", "xm-tools").unwrap();
+        let pinyin = score("xm", "项目").unwrap();
+        let subsequence = score("ytsrc", "ytools-src-tauri").unwrap();
+        let fuzzy = score("ytoolz", "ytools").unwrap();
+        assert_eq!(literal.kind, MatchKind::Literal);
+        assert_eq!(pinyin.kind, MatchKind::PinyinInitials);
+        let mixed = score("xiangmgl", "项目管理").unwrap();
+        assert_eq!(mixed.kind, MatchKind::PinyinMixed);
+        assert_eq!(subsequence.kind, MatchKind::Subsequence);
+        assert_eq!(fuzzy.kind, MatchKind::Fuzzy);
+        assert!(literal.score > pinyin.score);
+        assert!(pinyin.score > mixed.score);
+        assert!(mixed.score > subsequence.score);
+        assert!(subsequence.score > fuzzy.score);
+    }
+
+    #[test]
+    fn test_normalized_match_ignores_separators() {
+        let m = score("myproject", "my-project").unwrap();
+        assert_eq!(m.kind, MatchKind::Normalized);
+    }
+
+    #[test]
+    fn test_fuzzy_rejects_unrelated_names() {
+        assert!(score("my-project", "other-project").is_none());
+        assert!(score("zzzz", "ytools").is_none());
+    }
+
+    fn ranges(query: &str, name: &str) -> Vec<(usize, usize)> {
+        score(query, name)
+            .unwrap()
+            .highlights
+            .iter()
+            .map(|r| (r.start, r.end))
+            .collect()
+    }
+
+    #[test]
+    fn test_highlights_literal_prefers_word_start() {
+        // "api" 同时出现在中间和词首，高亮词首处
+        assert_eq!(ranges("api", "rapid-api"), vec![(6, 9)]);
+    }
+
+    #[test]
+    fn test_highlights_normalized_skips_separators() {
+        assert_eq!(ranges("myproj", "my-project"), vec![(0, 2), (3, 7)]);
+    }
+
+    #[test]
+    fn test_highlights_pinyin_map_to_chinese() {
+        // 全拼 "guanli" 对应 "管理"，首字母 "xm" 对应 "项目"
+        assert_eq!(ranges("guanli", "项目管理"), vec![(2, 4)]);
+        assert_eq!(ranges("xm", "新项目"), vec![(1, 3)]);
+        // 部分拼音也高亮整个汉字
+        assert_eq!(ranges("xiangm", "项目"), vec![(0, 2)]);
+    }
+
+    #[test]
+    fn test_highlights_fuzzy_subsequence() {
+        assert_eq!(ranges("ytoolz", "ytools"), vec![(0, 5)]);
+    }
+
+    #[test]
+    fn test_traditional_keeps_original_readings() {
+        // "乾" 的简体为 "干"，但仍应按自身读音 "qian" 匹配
+        assert_eq!(
+            score("qiankun", "乾坤").unwrap().kind,
+            MatchKind::PinyinFull
+        );
+        assert_eq!(
+            score("qianlong", "乾隆").unwrap().kind,
+            MatchKind::PinyinFull
+        );
+        assert!(score("gankun", "乾坤").is_some());
+        // 混合输入时繁体原字与简体字都能匹配
+        assert!(score("乾long", "乾隆").is_some());
+        assert!(score("干long", "乾隆").is_some());
+    }
+
+    #[test]
+    fn test_merge_ranges() {
+        let merged = merge_ranges(vec![4, 1, 2, 2, 7]);
+        let pairs: Vec<(usize, usize)> = merged.iter().map(|r| (r.start, r.end)).collect();
+        assert_eq!(pairs, vec![(1, 3), (4, 5), (7, 8)]);
+    }
+
+    #[test]
+    fn test_long_polyphone_name_is_bounded() {
+        // 48 个多音字，完整展开会有 2^48 种读音组合；预计算的组合数有上限，匹配走读音网格不展开组合
+        let name: String = "行重长".repeat(16);
+        let entry = IndexedWorkspace::new(&Path::new("/tmp").join(&name), name.clone());
+        assert!(entry.pinyin_full.len() <= crate::search::MAX_PINYIN_VARIANTS);
+        assert!(entry.pinyin_initials.len() <= crate::search::MAX_PINYIN_VARIANTS);
+        assert_eq!(entry.readings.len(), 48);
+
+        // 匹配失败的状态会被记忆，每个 (字符, 查询位置) 至多展开一次，不会随组合数指数增长
+        let queries = [
+            "hangchongzhang",
+            "xingzhongchangxing",
+            "xzcxzc",
+            "hzchzc",
+            "xingzchanghang",
+            "zhangxingzhongzz",
+            "hangchongzhangq",
+        ];
+        for query in queries {
+            score_entry(&PreparedQuery::new(query), &entry);
+        }
+
+        // 每种读音都能被匹配到
+        let last = score("chongzhanghang", &name).unwrap();
+        assert_eq!(last.kind, MatchKind::PinyinFull);
+    }
+
+    #[test]
+    fn test_longest_common_substring_len() {
+        assert_eq!(longest_common_substring_len("ytools", "tool"), 4);
+        assert_eq!(longest_common_substring_len("abc", ""), 0);
+    }
+
+    #[test]
+    fn test_longest_common_subsequence_len() {
+        assert_eq!(longest_common_subsequence_len("ytoolssrc", "ytsrc"), 5);
+        assert_eq!(longest_common_subsequence_len("abc", "xyz"), 0);
+    }
+
+    #[test]
+    fn test_levenshtein_distance() {
+        assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
+        assert_eq!(levenshtein_distance("", "abc"), 3);
+        assert_eq!(levenshtein_distance("same", "same"), 0);
+    }
+}
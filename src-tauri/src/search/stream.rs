@@ -0,0 +1,324 @@
+//! 流式搜索
+//!
+//! 工作区搜索与文件名搜索的异步版本：命令立即返回，搜索在后台线程中逐个搜索目录进行，
+//! 每完成一个目录就通过 `search-partial` 事件推送该目录的结果，全部完成后通过 `search-done`
+//! 推送合并排序后的最终结果。新的请求会取消上一次尚未完成的请求（见 `tasks` 模块），
+//! 被取消的请求不再推送部分结果，结束事件中 `cancelled` 为 true。
+//! 部分结果与结束事件都带有搜索目录的状态（不存在、无权限、超时），遍历超时的目录不会阻塞后续目录。
+
+use super::discovery::into_directories;
+use super::files::{FileSearchOptions, FileWalks};
+use super::query::ParsedQuery;
+use super::roots::RootStatus;
+use super::tasks::{CancelToken, SearchTasks, TaskKind};
+use super::{
+    refresh_git_status, score_candidates, sort_hits, with_git_status, FrecencyStore,
+    IndexedWorkspace, SearchDirectory, SearchDirectoryInput, WorkspaceHit, WorkspaceIndex,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::Emitter;
+
+/// 单个搜索目录完成事件
+pub const SEARCH_PARTIAL_EVENT: &str = "search-partial";
+/// 搜索结束事件
+pub const SEARCH_DONE_EVENT: &str = "search-done";
+
+/// 搜索对象
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SearchMode {
+    /// 工作区文件夹（使用索引）
+    #[default]
+    Workspaces,
+    /// 文件名
+    Files,
+}
+
+/// 流式搜索请求
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartSearchRequest {
+    /// 前端生成的请求 id，事件中原样带回
+    pub request_id: u64,
+    #[serde(default)]
+    pub mode: SearchMode,
+    pub query: String,
+    pub directories: Vec<SearchDirectoryInput>,
+    #[serde(default)]
+    pub options: FileSearchOptions,
+}
+
+/// 单个搜索目录的部分结果
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchPartial {
+    pub request_id: u64,
+    /// 搜索目录及其状态
+    #[serde(flatten)]
+    pub status: RootStatus,
+    /// 该目录下排序后的结果
+    pub hits: Vec<WorkspaceHit>,
+}
+
+/// 搜索结束事件的负载
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchDone {
+    pub request_id: u64,
+    /// 所有搜索目录合并排序后的结果（被取消时为已完成目录的结果）
+    pub hits: Vec<WorkspaceHit>,
+    /// 已完成的搜索目录的状态
+    pub roots: Vec<RootStatus>,
+    pub cancelled: bool,
+}
+
+/// 在后台搜索工作区或文件，结果通过事件推送
+///
+/// `options` 中的遍历深度与文件数上限只在文件模式下生效。
+#[tauri::command]
+pub fn start_search(
+    app: tauri::AppHandle,
+    index: tauri::State<'_, WorkspaceIndex>,
+    frecency: tauri::State<'_, FrecencyStore>,
+    walks: tauri::State<'_, FileWalks>,
+    tasks: tauri::State<'_, SearchTasks>,
+    request: StartSearchRequest,
+) -> Result<(), String> {
+    let index = WorkspaceIndex::clone(&index);
+    let walks = FileWalks::clone(&walks);
+    let frecency_scores = frecency.scores();
+    let directories = into_directories(request.directories);
+    let cancel = tasks.start(TaskKind::Search, request.request_id);
+    let tasks = SearchTasks::clone(&tasks);
+
+    std::thread::spawn(move || {
+        let search = StreamSearch {
+            request_id: request.request_id,
+            mode: request.mode,
+            index: &index,
+            walks: &walks,
+            frecency_scores: &frecency_scores,
+            query: &request.query,
+            options: &request.options,
+            cancel: &cancel,
+        };
+        let done = search.run(&directories, |partial| {
+            let _ = app.emit(SEARCH_PARTIAL_EVENT, partial);
+        });
+        // 先推送结束事件，之后读取完成的 Git 状态不会被结束事件中的结果覆盖
+        let _ = app.emit(SEARCH_DONE_EVENT, &done);
+        if request.mode == SearchMode::Workspaces {
+            refresh_git_status(&app, &index, &done.hits);
+        }
+        tasks.finish(TaskKind::Search, request.request_id);
+    });
+    Ok(())
+}
+
+/// 一次流式搜索的参数
+pub(crate) struct StreamSearch<'a> {
+    pub request_id: u64,
+    pub mode: SearchMode,
+    pub index: &'a WorkspaceIndex,
+    pub walks: &'a FileWalks,
+    pub frecency_scores: &'a HashMap<String, i64>,
+    pub query: &'a str,
+    pub options: &'a FileSearchOptions,
+    pub cancel: &'a CancelToken,
+}
+
+impl StreamSearch<'_> {
+    /// 逐个搜索目录执行搜索，每完成一个目录调用一次 `on_partial`
+    ///
+    /// 等待单个目录（索引重建、文件遍历）期间也会检查取消，被取消的请求不必等到目录超时。
+    pub fn run(
+        &self,
+        directories: &[SearchDirectory],
+        mut on_partial: impl FnMut(SearchPartial),
+    ) -> SearchDone {
+        let limit = self.options.search.effective_limit();
+        let timeout = self.options.search.root_timeout();
+        let mut done = SearchDone {
+            request_id: self.request_id,
+            ..Default::default()
+        };
+        if self.mode == SearchMode::Workspaces {
+            self.index.prune(directories);
+        }
+
+        let parsed = ParsedQuery::parse(self.query);
+        let mut all = Vec::new();
+        let mut remaining_files = self.options.effective_max_files();
+        for dir in directories.iter().filter(|dir| parsed.includes_root(dir)) {
+            if self.cancel.is_cancelled() {
+                done.cancelled = true;
+                break;
+            }
+
+            let (status, scored) = match self.mode {
+                SearchMode::Workspaces => {
+                    let entries = self.index.root_entries(dir, timeout, self.cancel);
+                    let status = RootStatus::from_result(&dir.path, &entries, |e| e.len());
+                    (
+                        status,
+                        self.score_root(dir, entries.as_deref().map_or(&[], Vec::as_slice)),
+                    )
+                }
+                SearchMode::Files => {
+                    let entries =
+                        self.walks
+                            .root_files(dir, self.options, remaining_files, self.cancel);
+                    let status = RootStatus::from_result(&dir.path, &entries, Vec::len);
+                    remaining_files = remaining_files.saturating_sub(status.entries);
+                    (
+                        status,
+                        self.score_root(dir, entries.as_deref().unwrap_or_default()),
+                    )
+                }
+            };
+
+            // 目录处理期间被取消时，不再推送已过期的结果
+            if self.cancel.is_cancelled() {
+                done.cancelled = true;
+                break;
+            }
+            done.roots.push(status.clone());
+            on_partial(SearchPartial {
+                request_id: self.request_id,
+                status,
+                hits: self.finish(sort_hits(scored.clone(), limit)),
+            });
+            all.extend(scored);
+        }
+
+        done.hits = self.finish(sort_hits(all, limit));
+        done
+    }
+
+    /// 工作区模式下为结果补充 Git 状态
+    fn finish(&self, hits: Vec<WorkspaceHit>) -> Vec<WorkspaceHit> {
+        match self.mode {
+            SearchMode::Workspaces => with_git_status(self.index, hits),
+            SearchMode::Files => hits,
+        }
+    }
+
+    /// 对单个搜索目录的条目评分
+    fn score_root(
+        &self,
+        dir: &SearchDirectory,
+        entries: &[IndexedWorkspace],
+    ) -> Vec<(i64, WorkspaceHit)> {
+        score_candidates(
+            self.frecency_scores,
+            self.query,
+            &self.options.search,
+            entries.iter().map(|entry| (dir, entry)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::roots::RootState;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn run(
+        mode: SearchMode,
+        query: &str,
+        directories: &[SearchDirectory],
+        cancel: &CancelToken,
+    ) -> (Vec<SearchPartial>, SearchDone) {
+        let index = WorkspaceIndex::new();
+        let walks = FileWalks::new();
+        let scores = HashMap::new();
+        let options = FileSearchOptions::default();
+        let search = StreamSearch {
+            request_id: 42,
+            mode,
+            index: &index,
+            walks: &walks,
+            frecency_scores: &scores,
+            query,
+            options: &options,
+            cancel,
+        };
+        let mut partials = Vec::new();
+        let done = search.run(directories, |partial| partials.push(partial));
+        (partials, done)
+    }
+
+    fn two_roots() -> (TempDir, Vec<SearchDirectory>) {
+        let temp_dir = TempDir::new().unwrap();
+        let first = temp_dir.path().join("first");
+        let second = temp_dir.path().join("second");
+        fs::create_dir_all(first.join("api-server")).unwrap();
+        fs::create_dir_all(second.join("api")).unwrap();
+        fs::create_dir_all(second.join("web")).unwrap();
+        fs::write(second.join("web/api.ts"), "").unwrap();
+        let directories = vec![
+            SearchDirectory::new(first.to_string_lossy()),
+            SearchDirectory::new("/nonexistent/path"),
+            SearchDirectory::new(second.to_string_lossy()),
+        ];
+        (temp_dir, directories)
+    }
+
+    #[test]
+    fn test_stream_emits_partial_per_root_and_merged_done() {
+        let (_temp_dir, directories) = two_roots();
+        let (partials, done) = run(
+            SearchMode::Workspaces,
+            "api",
+            &directories,
+            &CancelToken::default(),
+        );
+
+        let roots: Vec<&str> = partials.iter().map(|p| p.status.root.as_str()).collect();
+        assert_eq!(
+            roots,
+            vec![
+                directories[0].path.as_str(),
+                "/nonexistent/path",
+                directories[2].path.as_str()
+            ]
+        );
+        assert!(partials.iter().all(|p| p.request_id == 42));
+        assert!(partials[1].hits.is_empty());
+        assert_eq!(partials[1].status.state, RootState::Missing);
+        assert_eq!(partials[2].status.entries, 2);
+        assert_eq!(done.roots.len(), 3);
+
+        // 最终结果合并所有目录并重新排序：完全匹配的 api 排在前面
+        let names: Vec<&str> = done.hits.iter().map(|h| h.name.as_str()).collect();
+        assert_eq!(names, vec!["api", "api-server"]);
+        assert!(!done.cancelled);
+    }
+
+    #[test]
+    fn test_stream_file_mode() {
+        let (_temp_dir, directories) = two_roots();
+        let (_, done) = run(
+            SearchMode::Files,
+            "api.ts",
+            &directories,
+            &CancelToken::default(),
+        );
+        assert_eq!(done.hits.len(), 1);
+        assert!(done.hits[0].path.ends_with("api.ts"));
+    }
+
+    #[test]
+    fn test_stream_cancelled_request_emits_nothing() {
+        let (_temp_dir, directories) = two_roots();
+        let cancel = CancelToken::default();
+        cancel.cancel();
+        let (partials, done) = run(SearchMode::Workspaces, "api", &directories, &cancel);
+        assert!(partials.is_empty());
+        assert!(done.cancelled);
+        assert!(done.hits.is_empty());
+    }
+}
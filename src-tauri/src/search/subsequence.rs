@@ -0,0 +1,174 @@
+//! fzf 风格的子序列匹配
+//!
+//! 查询的每个字符按顺序出现在名称中即视为匹配（如 `ytsrc` 匹配 `ytools-src-tauri`）。
+//! 通过动态规划在所有可能的对齐方式中选出得分最高的一种：落在词首（分隔符之后、驼峰大写）
+//! 和连续命中的字符加分，字符之间的间隔按长度扣分。
+
+/// 每个命中字符的基础分
+const SCORE_MATCH: i64 = 16;
+/// 间隔开始的扣分
+const GAP_START_PENALTY: i64 = 3;
+/// 间隔每延长一个字符的扣分
+const GAP_EXTENSION_PENALTY: i64 = 1;
+/// 命中名称开头或分隔符之后的字符
+const BONUS_BOUNDARY: i64 = 8;
+/// 命中驼峰大写或字母数字交界处的字符
+const BONUS_CAMEL: i64 = 7;
+/// 与上一个命中字符相邻
+const BONUS_CONSECUTIVE: i64 = GAP_START_PENALTY + GAP_EXTENSION_PENALTY;
+/// 查询首字符的位置加分倍数（首字符落在词首最能体现意图）
+const FIRST_CHAR_MULTIPLIER: i64 = 2;
+
+/// 不可达状态
+const UNREACHABLE: i64 = i64::MIN / 2;
+
+/// 子序列匹配结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SubsequenceMatch {
+    /// 匹配得分（未叠加匹配方式的基础分）
+    pub score: i64,
+    /// 查询各字符在名称中的命中位置
+    pub positions: Vec<usize>,
+}
+
+/// 在名称中查找查询的最佳子序列对齐
+///
+/// `name` 与 `query` 均应为小写字符序列，`word_starts` 与 `name` 一一对应。
+pub(crate) fn match_subsequence(
+    name: &[char],
+    word_starts: &[bool],
+    query: &[char],
+) -> Option<SubsequenceMatch> {
+    if query.is_empty() || !is_subsequence(name, query) {
+        return None;
+    }
+
+    let n = query.len();
+    let m = name.len();
+    let bonus: Vec<i64> = (0..m)
+        .map(|j| position_bonus(name, word_starts, j))
+        .collect();
+
+    // score[i][j]：查询前 i+1 个字符已匹配且第 i 个字符命中 name[j] 时的最高得分
+    let mut score = vec![vec![UNREACHABLE; m]; n];
+    let mut from = vec![vec![usize::MAX; m]; n];
+
+    for (j, &c) in name.iter().enumerate() {
+        if c == query[0] {
+            score[0][j] = SCORE_MATCH + bonus[j] * FIRST_CHAR_MULTIPLIER;
+        }
+    }
+
+    for i in 1..n {
+        let (done, rest) = score.split_at_mut(i);
+        let prev_row = &done[i - 1];
+        let row = &mut rest[0];
+        for j in i..m {
+            if name[j] != query[i] {
+                continue;
+            }
+            let mut best = UNREACHABLE;
+            let mut best_from = usize::MAX;
+            for (k, &prev) in prev_row.iter().enumerate().take(j).skip(i - 1) {
+                if prev == UNREACHABLE {
+                    continue;
+                }
+                let gap = j - k - 1;
+                let transition = if gap == 0 {
+                    BONUS_CONSECUTIVE
+                } else {
+                    -(GAP_START_PENALTY + GAP_EXTENSION_PENALTY * (gap as i64 - 1))
+                };
+                if prev + transition > best {
+                    best = prev + transition;
+                    best_from = k;
+                }
+            }
+            if best != UNREACHABLE {
+                row[j] = best + SCORE_MATCH + bonus[j];
+                from[i][j] = best_from;
+            }
+        }
+    }
+
+    // 取最后一个查询字符的最佳命中位置，沿回溯指针还原整条对齐
+    let (mut j, &best) = score[n - 1]
+        .iter()
+        .enumerate()
+        .filter(|(_, &s)| s != UNREACHABLE)
+        .max_by_key(|(j, &s)| (s, std::cmp::Reverse(*j)))?;
+
+    let mut positions = vec![0; n];
+    for i in (0..n).rev() {
+        positions[i] = j;
+        j = from[i][j];
+    }
+
+    Some(SubsequenceMatch {
+        score: best,
+        positions,
+    })
+}
+
+/// 快速判断查询是否为名称的子序列
+fn is_subsequence(name: &[char], query: &[char]) -> bool {
+    let mut rest = query.iter().peekable();
+    for c in name {
+        if rest.peek() == Some(&c) {
+            rest.next();
+        }
+    }
+    rest.peek().is_none()
+}
+
+/// 名称中某一位置的词首加分
+fn position_bonus(name: &[char], word_starts: &[bool], j: usize) -> i64 {
+    if j == 0 || !name[j - 1].is_alphanumeric() {
+        BONUS_BOUNDARY
+    } else if word_starts[j] {
+        BONUS_CAMEL
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::scoring::{lower_char, word_starts};
+    use super::*;
+
+    fn run(query: &str, name: &str) -> Option<SubsequenceMatch> {
+        let chars: Vec<char> = name.chars().collect();
+        let lower: Vec<char> = chars.iter().copied().map(lower_char).collect();
+        let query: Vec<char> = query.chars().collect();
+        match_subsequence(&lower, &word_starts(&chars), &query)
+    }
+
+    #[test]
+    fn test_subsequence_requires_all_chars_in_order() {
+        assert!(run("ytsrc", "ytools-src-tauri").is_some());
+        assert!(run("crs", "ytools-src-tauri").is_none());
+        assert!(run("", "ytools").is_none());
+    }
+
+    #[test]
+    fn test_subsequence_prefers_word_boundaries() {
+        // "src" 应对齐到单独的单词，而不是散落在前面的字符中
+        let m = run("ytsrc", "ytools-src-tauri").unwrap();
+        assert_eq!(m.positions, vec![0, 1, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_subsequence_camel_case_bonus() {
+        let m = run("fb", "FooBar").unwrap();
+        assert_eq!(m.positions, vec![0, 3]);
+        assert!(m.score > run("fb", "foobar").unwrap().score);
+    }
+
+    #[test]
+    fn test_subsequence_penalizes_gaps() {
+        let compact = run("abc", "abcxxxxx").unwrap().score;
+        let spread = run("abc", "axxbxxcx").unwrap().score;
+        assert!(compact > spread);
+    }
+}
@@ -0,0 +1,436 @@
+//! 拼音读音网格匹配
+//!
+//! 按名称中的每个字符记录其所有读音（多音字有多个），组成一张“字符 × 读音”的网格。
+//! 全拼、首字母以及混合输入（如 `xiangmgl`、`xmguanli`）都在这张网格上匹配，
+//! 搜索状态只有“字符位置 × 查询位置”两维，并记忆失败状态，
+//! 因此多音字很多的长名称也无需展开所有读音组合。
+//! 繁体字和日文假名的读音由 `transliterate` 提供，同样在这张网格上匹配。
+
+use super::fuzzy_pinyin::fuzzy_spellings;
+use super::transliterate::{is_kana, kana_readings, pinyin_syllables, to_simplified};
+
+/// 名称中单个字符的可选读法
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CharReadings {
+    /// 在原名称中的字符下标
+    pub source: usize,
+    /// 小写形式
+    pub literal: char,
+    /// 小写形式转为简体字（非繁体字时与 `literal` 相同）
+    pub simplified: char,
+    /// 所有读音：汉字的拼音及其模糊拼写、假名的罗马字（其他字符为空）
+    pub readings: Vec<Reading>,
+}
+
+/// 单个读音的拼写
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Reading {
+    pub letters: Vec<char>,
+    /// 该拼写依赖的模糊拼音规则，0 表示标准拼写
+    pub rules: u8,
+}
+
+impl CharReadings {
+    fn is_phonetic(&self) -> bool {
+        !self.readings.is_empty()
+    }
+
+    fn is_separator(&self) -> bool {
+        !self.literal.is_alphanumeric()
+    }
+}
+
+/// 为名称的每个字符生成可选读法
+pub(crate) fn char_readings(name: &str) -> Vec<CharReadings> {
+    let chars: Vec<char> = name.chars().collect();
+    chars
+        .iter()
+        .enumerate()
+        .map(|(source, &c)| {
+            let mut readings: Vec<Reading> = Vec::new();
+            // 标准读音在前，模糊拼写与已有拼写重复时保留标准读音
+            for (spelling, rules) in spellings(&chars, source) {
+                let letters: Vec<char> = spelling.chars().collect();
+                if !readings.iter().any(|reading| reading.letters == letters) {
+                    readings.push(Reading { letters, rules });
+                }
+            }
+            let literal = c.to_lowercase().next().unwrap_or(c);
+            CharReadings {
+                source,
+                literal,
+                simplified: to_simplified(literal),
+                readings,
+            }
+        })
+        .collect()
+}
+
+/// 字符的所有拼写及其依赖的模糊拼音规则：汉字为拼音（繁体字含简体字的读音），假名为罗马字
+fn spellings(chars: &[char], index: usize) -> Vec<(String, u8)> {
+    let c = chars[index];
+    if c.is_ascii() {
+        return Vec::new();
+    }
+    if is_kana(c) {
+        return kana_readings(chars, index)
+            .into_iter()
+            .map(|reading| (reading, 0))
+            .collect();
+    }
+    let syllables = pinyin_syllables(c);
+    syllables
+        .iter()
+        .map(|syllable| (syllable.to_string(), 0))
+        .chain(
+            syllables
+                .iter()
+                .flat_map(|syllable| fuzzy_spellings(syllable)),
+        )
+        .collect()
+}
+
+/// 网格匹配方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SyllableMode {
+    /// 全拼：查询是拼音全拼串（ASCII 字符原样保留）的子串
+    Full,
+    /// 首字母：每个汉字（或假名）取读音首字母，ASCII 字母原样保留，其余字符忽略
+    Initials,
+    /// 混合：每个汉字（或假名）可任选完整音节、音节前缀或首字母，分隔符可省略
+    Mixed,
+}
+
+/// 网格匹配结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SyllableMatch {
+    /// 是否从名称的第一个有效字符开头匹配
+    pub prefix: bool,
+    /// 是否恰好覆盖了名称的所有有效字符
+    pub complete: bool,
+    /// 命中的原名称字符下标
+    pub positions: Vec<usize>,
+    /// 名称中参与该方式匹配的有效字符数
+    pub significant: usize,
+    /// 使用模糊拼音拼写命中的字符数
+    pub fuzzy: usize,
+}
+
+/// 在读音网格上匹配查询，从左到右尝试每个起点，返回第一个成功的匹配
+///
+/// `fuzzy_rules` 为开启的模糊拼音规则掩码，只有依赖规则全部开启的模糊拼写才参与匹配。
+pub(crate) fn match_syllables(
+    chars: &[CharReadings],
+    query: &[char],
+    mode: SyllableMode,
+    fuzzy_rules: u8,
+) -> Option<SyllableMatch> {
+    if query.is_empty() {
+        return None;
+    }
+
+    let significant: Vec<usize> = (0..chars.len())
+        .filter(|&i| !is_transparent(&chars[i], mode))
+        .collect();
+    let matcher = Matcher {
+        chars,
+        query,
+        mode,
+        fuzzy_rules,
+        failed: vec![false; (chars.len() + 1) * (query.len() + 1)],
+        positions: Vec::new(),
+        fuzzy: 0,
+        end: 0,
+    };
+    matcher.run(&significant)
+}
+
+/// 字符在该匹配方式下是否不参与匹配（直接跳过）
+fn is_transparent(c: &CharReadings, mode: SyllableMode) -> bool {
+    match mode {
+        // 全拼保留所有 ASCII 字符，忽略没有拼音的非 ASCII 字符
+        SyllableMode::Full => !c.literal.is_ascii() && !c.is_phonetic(),
+        SyllableMode::Initials => !c.is_phonetic() && !c.literal.is_ascii_alphabetic(),
+        SyllableMode::Mixed => c.is_separator(),
+    }
+}
+
+struct Matcher<'a> {
+    chars: &'a [CharReadings],
+    query: &'a [char],
+    mode: SyllableMode,
+    fuzzy_rules: u8,
+    /// failed[i * (n + 1) + p]：从字符 i、查询位置 p 出发已确认无法完成匹配
+    failed: Vec<bool>,
+    positions: Vec<usize>,
+    /// 当前路径上使用模糊拼写的字符数
+    fuzzy: usize,
+    /// 最后一个被完整消耗的字符之后的位置
+    end: usize,
+}
+
+impl Matcher<'_> {
+    fn run(mut self, significant: &[usize]) -> Option<SyllableMatch> {
+        for (rank, &start) in significant.iter().enumerate() {
+            // 全拼允许从某个音节的中间开始（子串语义），偏移为 0 时才算前缀
+            let offsets = match self.mode {
+                SyllableMode::Full => self.max_reading_len(start),
+                _ => 1,
+            };
+            for offset in 0..offsets {
+                self.positions.clear();
+                self.fuzzy = 0;
+                if self.step(start, 0, offset) {
+                    let last = significant.last().copied().unwrap_or(0);
+                    return Some(SyllableMatch {
+                        prefix: rank == 0 && offset == 0,
+                        complete: rank == 0 && offset == 0 && self.end > last,
+                        positions: std::mem::take(&mut self.positions),
+                        significant: significant.len(),
+                        fuzzy: self.fuzzy,
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    fn max_reading_len(&self, i: usize) -> usize {
+        let c = &self.chars[i];
+        if c.is_phonetic() {
+            c.readings
+                .iter()
+                .map(|reading| reading.letters.len())
+                .max()
+                .unwrap_or(1)
+        } else {
+            1
+        }
+    }
+
+    /// 尝试让字符 `i` 从读音的第 `offset` 个字母起消耗查询的 `p..`
+    fn step(&mut self, i: usize, p: usize, offset: usize) -> bool {
+        if p == self.query.len() {
+            return true;
+        }
+        if i == self.chars.len() {
+            return false;
+        }
+        let key = i * (self.query.len() + 1) + p;
+        if offset == 0 && self.failed[key] {
+            return false;
+        }
+
+        let chars = self.chars;
+        let c = &chars[i];
+        if is_transparent(c, self.mode) {
+            // 起点不会是被跳过的字符，这里只可能出现在匹配中间
+            return self.step(i + 1, p, 0);
+        }
+
+        let literals = [c.literal, c.simplified];
+        let readings: Vec<(&[char], bool)> = if c.is_phonetic() {
+            c.readings
+                .iter()
+                .filter(|reading| reading.rules & !self.fuzzy_rules == 0)
+                .map(|reading| (reading.letters.as_slice(), reading.rules != 0))
+                .collect()
+        } else if c.literal == c.simplified {
+            vec![(&literals[..1], false)]
+        } else {
+            vec![(&literals[..1], false), (&literals[1..], false)]
+        };
+        let rest = self.query.len() - p;
+
+        for (reading, fuzzy) in readings {
+            let candidates: Vec<usize> = match self.mode {
+                // 全拼：必须消耗完整个音节，除非查询在此结束
+                SyllableMode::Full => {
+                    if offset >= reading.len() {
+                        continue;
+                    }
+                    let full = reading.len() - offset;
+                    if full <= rest {
+                        vec![full]
+                    } else {
+                        vec![rest]
+                    }
+                }
+                SyllableMode::Initials => vec![1],
+                // 混合：任意非空前缀，优先更长的前缀
+                SyllableMode::Mixed => (1..=reading.len().min(rest)).rev().collect(),
+            };
+            for len in candidates {
+                let piece = &reading[offset..offset + len];
+                if piece != &self.query[p..p + len] {
+                    continue;
+                }
+                self.positions.push(c.source);
+                // 只有完整消耗了读音（首字母模式下即为该字母）才算覆盖到该字符末尾
+                self.end = if self.mode == SyllableMode::Initials || offset + len == reading.len() {
+                    i + 1
+                } else {
+                    i
+                };
+                self.fuzzy += usize::from(fuzzy);
+                if self.step(i + 1, p + len, 0) {
+                    return true;
+                }
+                self.fuzzy -= usize::from(fuzzy);
+                self.positions.pop();
+            }
+        }
+
+        // 混合模式下汉字本身也可以直接输入（原字与简体字均可）
+        if self.mode == SyllableMode::Mixed
+            && c.is_phonetic()
+            && (c.literal == self.query[p] || c.simplified == self.query[p])
+        {
+            self.positions.push(c.source);
+            self.end = i + 1;
+            if self.step(i + 1, p + 1, 0) {
+                return true;
+            }
+            self.positions.pop();
+        }
+
+        if offset == 0 {
+            self.failed[key] = true;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::fuzzy_pinyin::FuzzyPinyinRules;
+    use super::*;
+
+    fn matches(query: &str, name: &str, mode: SyllableMode) -> Option<Vec<usize>> {
+        let query: Vec<char> = query.chars().collect();
+        match_syllables(&char_readings(name), &query, mode, 0).map(|m| m.positions)
+    }
+
+    fn mixed(query: &str, name: &str) -> Option<Vec<usize>> {
+        matches(query, name, SyllableMode::Mixed)
+    }
+
+    #[test]
+    fn test_mixed_full_syllables_and_initials() {
+        assert_eq!(mixed("xiangmgl", "项目管理"), Some(vec![0, 1, 2, 3]));
+        assert_eq!(mixed("xmguanli", "项目管理"), Some(vec![0, 1, 2, 3]));
+        assert_eq!(mixed("xiangmuguanli", "项目管理"), Some(vec![0, 1, 2, 3]));
+        assert_eq!(mixed("xmgl", "项目管理"), Some(vec![0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn test_partial_syllables_and_offsets() {
+        // 部分音节、从中间开始
+        assert_eq!(mixed("guanl", "项目管理"), Some(vec![2, 3]));
+        assert_eq!(mixed("xiamuguan", "项目管理"), Some(vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn test_mixed_with_latin_and_separators() {
+        // 拉丁字母按原样匹配，分隔符可以省略
+        assert_eq!(
+            mixed("apixmgl", "api-项目管理"),
+            Some(vec![0, 1, 2, 4, 5, 6, 7])
+        );
+        assert_eq!(mixed("项mgl", "项目管理"), Some(vec![0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn test_polyphone_readings() {
+        // 行 有 xing/hang 两种读音
+        assert!(mixed("yhang", "银行").is_some());
+        assert!(mixed("yinxing", "银行").is_some());
+    }
+
+    #[test]
+    fn test_rejects_non_matching() {
+        assert!(mixed("xmglz", "项目管理").is_none());
+        // 每个字符至少要消耗一个查询字符，不能跳过汉字
+        assert!(mixed("xgl", "项目管理").is_none());
+    }
+
+    #[test]
+    fn test_full_mode_is_substring_of_pinyin() {
+        let full = |q: &str, name: &str| matches(q, name, SyllableMode::Full);
+        assert_eq!(full("xiangmu", "项目管理"), Some(vec![0, 1]));
+        // 可以从音节中间开始、在音节中间结束
+        assert_eq!(full("angmuguan", "项目管理"), Some(vec![0, 1, 2]));
+        assert_eq!(full("mugu", "项目管理"), Some(vec![1, 2]));
+        // 中间的音节必须完整
+        assert!(full("xiangmguanli", "项目管理").is_none());
+        // ASCII 分隔符在全拼中保留
+        assert_eq!(full("api-xiang", "api-项目"), Some(vec![0, 1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn test_initials_mode() {
+        let initials = |q: &str, name: &str| matches(q, name, SyllableMode::Initials);
+        assert_eq!(initials("mgl", "项目管理"), Some(vec![1, 2, 3]));
+        // 首字母模式忽略分隔符与数字
+        assert_eq!(initials("vxm", "v2-项目"), Some(vec![0, 3, 4]));
+        // 多音字任一读音的首字母都可以
+        assert!(initials("yh", "银行").is_some());
+        assert!(initials("yx", "银行").is_some());
+        assert!(initials("xiang", "项目").is_none());
+    }
+
+    #[test]
+    fn test_prefix_and_complete_flags() {
+        let chars = char_readings("项目管理");
+        let q = |s: &str| s.chars().collect::<Vec<char>>();
+        let m = match_syllables(&chars, &q("xmgl"), SyllableMode::Initials, 0).unwrap();
+        assert!(m.prefix && m.complete);
+        let m = match_syllables(&chars, &q("xm"), SyllableMode::Initials, 0).unwrap();
+        assert!(m.prefix && !m.complete);
+        let m = match_syllables(&chars, &q("iangmu"), SyllableMode::Full, 0).unwrap();
+        assert!(!m.prefix);
+    }
+
+    #[test]
+    fn test_fuzzy_pinyin_rules_are_opt_in() {
+        let chars = char_readings("中心");
+        let query: Vec<char> = "zongxing".chars().collect();
+        let zh_z = FuzzyPinyinRules {
+            zh_z: true,
+            ..Default::default()
+        };
+        let both = FuzzyPinyinRules {
+            in_ing: true,
+            ..zh_z
+        };
+
+        // 默认不开启，只开启一条规则也不够
+        assert!(match_syllables(&chars, &query, SyllableMode::Full, 0).is_none());
+        assert!(match_syllables(&chars, &query, SyllableMode::Full, zh_z.mask()).is_none());
+        let m = match_syllables(&chars, &query, SyllableMode::Full, both.mask()).unwrap();
+        assert_eq!(m.positions, vec![0, 1]);
+        assert_eq!(m.fuzzy, 2);
+    }
+
+    #[test]
+    fn test_traditional_chinese_readings() {
+        // 繁体字转为简体后查拼音，简体字也可以直接匹配繁体名称
+        assert_eq!(mixed("xmgl", "項目管理"), Some(vec![0, 1, 2, 3]));
+        assert_eq!(mixed("项目", "項目管理"), Some(vec![0, 1]));
+        let full = |q: &str, name: &str| matches(q, name, SyllableMode::Full);
+        assert_eq!(full("kaifa", "開發"), Some(vec![0, 1]));
+    }
+
+    #[test]
+    fn test_kana_romaji_readings() {
+        let full = |q: &str, name: &str| matches(q, name, SyllableMode::Full);
+        assert_eq!(full("sakura", "さくら"), Some(vec![0, 1, 2]));
+        assert_eq!(full("toukyou", "トウキョウ"), Some(vec![0, 1, 2, 3, 4]));
+        assert_eq!(full("gakkou", "がっこう"), Some(vec![0, 1, 2, 3]));
+        assert_eq!(full("kohi", "コーヒー"), None);
+        assert_eq!(full("koohii", "コーヒー"), Some(vec![0, 1, 2, 3]));
+        // 训令式拼写与混合输入
+        assert!(full("tukue", "つくえ").is_some());
+        assert_eq!(mixed("skr", "さくら"), Some(vec![0, 1, 2]));
+    }
+}
@@ -0,0 +1,138 @@
+//! 后台搜索任务
+//!
+//! 耗时的搜索（流式工作区/文件搜索、内容搜索）在后台线程中执行，以前端传入的请求 id 区分。
+//! 同一类任务同时只运行一个：新的请求（如下一次按键）开始时会取消上一次尚未完成的请求。
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// 等待后台工作（遍历线程、目录重建）时检查取消的间隔
+pub(crate) const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// 任务类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum TaskKind {
+    /// 工作区或文件名搜索
+    Search,
+    /// 文件内容搜索
+    Content,
+}
+
+/// 取消标记，由任务在处理每个单元（搜索目录、文件）之前检查
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// 正在运行的搜索任务（作为 Tauri 托管状态共享，克隆后指向同一份登记，供后台线程结束时移出）
+#[derive(Clone, Default)]
+pub struct SearchTasks {
+    running: Arc<Mutex<HashMap<TaskKind, (u64, CancelToken)>>>,
+}
+
+impl SearchTasks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 登记新任务并取消同类的上一个任务，返回新任务的取消标记
+    pub(crate) fn start(&self, kind: TaskKind, request_id: u64) -> CancelToken {
+        let token = CancelToken::default();
+        if let Ok(mut running) = self.running.lock() {
+            if let Some((_, previous)) = running.insert(kind, (request_id, token.clone())) {
+                previous.cancel();
+            }
+        }
+        token
+    }
+
+    /// 任务结束后移出登记；该类别已被更新的请求替换时不做处理
+    pub(crate) fn finish(&self, kind: TaskKind, request_id: u64) {
+        if let Ok(mut running) = self.running.lock() {
+            if running.get(&kind).is_some_and(|(id, _)| *id == request_id) {
+                running.remove(&kind);
+            }
+        }
+    }
+
+    /// 取消指定请求，`request_id` 为空时取消所有任务
+    pub(crate) fn cancel(&self, request_id: Option<u64>) {
+        if let Ok(mut running) = self.running.lock() {
+            running.retain(|_, (id, token)| {
+                let matches = request_id.is_none_or(|request_id| request_id == *id);
+                if matches {
+                    token.cancel();
+                }
+                !matches
+            });
+        }
+    }
+}
+
+/// 取消后台搜索任务
+#[tauri::command]
+pub fn cancel_search(tasks: tauri::State<'_, SearchTasks>, request_id: Option<u64>) {
+    tasks.cancel(request_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_request_cancels_previous_of_same_kind() {
+        let tasks = SearchTasks::new();
+        let first = tasks.start(TaskKind::Search, 1);
+        let content = tasks.start(TaskKind::Content, 2);
+        let second = tasks.start(TaskKind::Search, 3);
+        assert!(first.is_cancelled());
+        assert!(!content.is_cancelled());
+        assert!(!second.is_cancelled());
+    }
+
+    #[test]
+    fn test_cancel_by_request_id_or_all() {
+        let tasks = SearchTasks::new();
+        let search = tasks.start(TaskKind::Search, 1);
+        let content = tasks.start(TaskKind::Content, 2);
+
+        // 已结束或不存在的请求 id 不影响当前任务
+        tasks.cancel(Some(99));
+        assert!(!search.is_cancelled());
+        tasks.cancel(Some(2));
+        assert!(content.is_cancelled());
+        assert!(!search.is_cancelled());
+        tasks.cancel(None);
+        assert!(search.is_cancelled());
+    }
+
+    #[test]
+    fn test_finished_tasks_are_removed() {
+        let tasks = SearchTasks::new();
+        tasks.start(TaskKind::Search, 1);
+        let second = tasks.start(TaskKind::Search, 2);
+        tasks.start(TaskKind::Content, 3);
+
+        // 已被替换的请求结束时不影响正在运行的请求
+        tasks.finish(TaskKind::Search, 1);
+        assert!(tasks
+            .running
+            .lock()
+            .unwrap()
+            .contains_key(&TaskKind::Search));
+        tasks.finish(TaskKind::Search, 2);
+        tasks.finish(TaskKind::Content, 3);
+        assert!(tasks.running.lock().unwrap().is_empty());
+        assert!(!second.is_cancelled());
+    }
+}
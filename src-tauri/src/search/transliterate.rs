@@ -0,0 +1,253 @@
+//! 繁体字与日文假名的转写
+//!
+//! 繁体字的读音为其本身与对应简体字读音的并集，假名转写为平文式罗马字（兼容训令式拼写），
+//! 两者都作为字符的读音接入拼音读音网格，与拼音全拼、首字母、混合输入共用同一套匹配。
+
+use super::traditional::TRADITIONAL_TO_SIMPLIFIED;
+use pinyin::ToPinyinMulti;
+
+/// 繁体字转换为简体字，其他字符原样返回
+pub(crate) fn to_simplified(c: char) -> char {
+    TRADITIONAL_TO_SIMPLIFIED
+        .binary_search_by_key(&c, |&(traditional, _)| traditional)
+        .map(|index| TRADITIONAL_TO_SIMPLIFIED[index].1)
+        .unwrap_or(c)
+}
+
+/// 字符的拼音（不带声调），依次为字符本身的读音和其简体字的读音，已去重
+///
+/// 繁简并非一一对应（如 乾 也是简体字，读 qian，对应的 干 只读 gan），只取简体字的读音会丢掉字符本身的读音。
+pub(crate) fn pinyin_syllables(c: char) -> Vec<&'static str> {
+    let mut syllables: Vec<&'static str> = Vec::new();
+    let simplified = to_simplified(c);
+    let sources = if simplified == c {
+        vec![c]
+    } else {
+        vec![c, simplified]
+    };
+    for pinyin in sources
+        .into_iter()
+        .filter_map(|source| source.to_pinyin_multi())
+        .flatten()
+    {
+        if !syllables.contains(&pinyin.plain()) {
+            syllables.push(pinyin.plain());
+        }
+    }
+    syllables
+}
+
+/// 是否为平假名或片假名（含长音符）
+pub(crate) fn is_kana(c: char) -> bool {
+    matches!(c, '\u{3041}'..='\u{3096}' | '\u{30A1}'..='\u{30FA}' | 'ー')
+}
+
+/// 片假名转换为对应的平假名
+fn to_hiragana(c: char) -> char {
+    match c {
+        '\u{30A1}'..='\u{30F6}' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+        _ => c,
+    }
+}
+
+/// 单个假名的罗马字拼写，第一个为平文式拼写
+fn romaji(c: char) -> &'static [&'static str] {
+    match to_hiragana(c) {
+        'あ' | 'ぁ' => &["a"],
+        'い' | 'ぃ' => &["i"],
+        'う' | 'ぅ' => &["u"],
+        'え' | 'ぇ' => &["e"],
+        'お' | 'ぉ' => &["o"],
+        'か' | 'ゕ' => &["ka"],
+        'き' => &["ki"],
+        'く' => &["ku"],
+        'け' | 'ゖ' => &["ke"],
+        'こ' => &["ko"],
+        'が' => &["ga"],
+        'ぎ' => &["gi"],
+        'ぐ' => &["gu"],
+        'げ' => &["ge"],
+        'ご' => &["go"],
+        'さ' => &["sa"],
+        'し' => &["shi", "si"],
+        'す' => &["su"],
+        'せ' => &["se"],
+        'そ' => &["so"],
+        'ざ' => &["za"],
+        'じ' => &["ji", "zi"],
+        'ず' => &["zu"],
+        'ぜ' => &["ze"],
+        'ぞ' => &["zo"],
+        'た' => &["ta"],
+        'ち' => &["chi", "ti"],
+        'つ' => &["tsu", "tu"],
+        'て' => &["te"],
+        'と' => &["to"],
+        'だ' => &["da"],
+        'ぢ' => &["ji", "di"],
+        'づ' => &["zu", "du"],
+        'で' => &["de"],
+        'ど' => &["do"],
+        'な' => &["na"],
+        'に' => &["ni"],
+        'ぬ' => &["nu"],
+        'ね' => &["ne"],
+        'の' => &["no"],
+        'は' => &["ha"],
+        'ひ' => &["hi"],
+        'ふ' => &["fu", "hu"],
+        'へ' => &["he"],
+        'ほ' => &["ho"],
+        'ば' => &["ba"],
+        'び' => &["bi"],
+        'ぶ' => &["bu"],
+        'べ' => &["be"],
+        'ぼ' => &["bo"],
+        'ぱ' => &["pa"],
+        'ぴ' => &["pi"],
+        'ぷ' => &["pu"],
+        'ぺ' => &["pe"],
+        'ぽ' => &["po"],
+        'ま' => &["ma"],
+        'み' => &["mi"],
+        'む' => &["mu"],
+        'め' => &["me"],
+        'も' => &["mo"],
+        'や' | 'ゃ' => &["ya"],
+        'ゆ' | 'ゅ' => &["yu"],
+        'よ' | 'ょ' => &["yo"],
+        'ら' => &["ra"],
+        'り' => &["ri"],
+        'る' => &["ru"],
+        'れ' => &["re"],
+        'ろ' => &["ro"],
+        'わ' | 'ゎ' => &["wa"],
+        'ゐ' => &["i", "wi"],
+        'ゑ' => &["e", "we"],
+        'を' => &["wo", "o"],
+        'ん' => &["n", "nn"],
+        'ゔ' => &["vu"],
+        _ => &[],
+    }
+}
+
+/// 拗音中的小写 ゃ/ゅ/ょ
+fn is_small_y(c: char) -> bool {
+    matches!(to_hiragana(c), 'ゃ' | 'ゅ' | 'ょ')
+}
+
+/// 名称中第 `index` 个字符（假名）的所有罗马字读音
+///
+/// 拗音、促音和长音依赖相邻的假名：`きょ` 读作 `k` + `yo`，`しゃ` 读作 `sh` + `a`，
+/// `っ` 取后一个假名的首个辅音（`って` → `t` + `te`），`ー` 重复前一个假名的元音。
+pub(crate) fn kana_readings(chars: &[char], index: usize) -> Vec<String> {
+    let c = chars[index];
+    let prev = index.checked_sub(1).map(|i| chars[i]);
+    let next = chars.get(index + 1).copied();
+    let primary = |c: Option<char>| c.and_then(|c| romaji(c).first().copied());
+
+    match to_hiragana(c) {
+        'っ' => match primary(next) {
+            Some(following) if following.starts_with("ch") => {
+                vec!["t".to_string(), "c".to_string()]
+            }
+            Some(following) => following.chars().take(1).map(String::from).collect(),
+            None => Vec::new(),
+        },
+        'ー' => primary(prev)
+            .and_then(|preceding| preceding.chars().last())
+            .map(|vowel| vec![vowel.to_string()])
+            .unwrap_or_default(),
+        small if is_small_y(small) => {
+            let mut readings: Vec<String> = romaji(small).iter().map(|r| r.to_string()).collect();
+            // しゃ/ちゃ/じゃ 的平文式拼写省略 y
+            if matches!(primary(prev), Some("shi" | "chi" | "ji")) {
+                readings.extend(readings.clone().iter().map(|r| r[1..].to_string()));
+            }
+            readings
+        }
+        _ => {
+            let mut readings: Vec<String> = romaji(c).iter().map(|r| r.to_string()).collect();
+            // 后接拗音时，い段假名只取辅音部分
+            if next.is_some_and(is_small_y) {
+                let stems: Vec<String> = readings
+                    .iter()
+                    .filter_map(|r| r.strip_suffix('i'))
+                    .filter(|stem| !stem.is_empty())
+                    .map(String::from)
+                    .collect();
+                readings.extend(stems);
+            }
+            readings
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn readings(name: &str, index: usize) -> Vec<String> {
+        let chars: Vec<char> = name.chars().collect();
+        kana_readings(&chars, index)
+    }
+
+    #[test]
+    fn test_table_is_sorted() {
+        assert!(TRADITIONAL_TO_SIMPLIFIED
+            .windows(2)
+            .all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn test_to_simplified() {
+        assert_eq!(to_simplified('項'), '项');
+        assert_eq!(to_simplified('開'), '开');
+        // 不常用的繁体字与扩展 A 区的字符同样能够转换
+        assert_eq!(to_simplified('鑰'), '钥');
+        assert_eq!(to_simplified('齲'), '龋');
+        assert_eq!(to_simplified('䊷'), '䌶');
+        assert_eq!(to_simplified('项'), '项');
+        assert_eq!(to_simplified('a'), 'a');
+    }
+
+    #[test]
+    fn test_pinyin_syllables_keep_original_readings() {
+        // 一简对多繁的字既保留自身读音，也包含简体字的读音
+        let syllables = pinyin_syllables('乾');
+        assert!(syllables.contains(&"qian"));
+        assert!(syllables.contains(&"gan"));
+        for c in ['乾', '於', '瞭', '著', '藉', '俱', '䬗', '䶧'] {
+            let syllables = pinyin_syllables(c);
+            for pinyin in c.to_pinyin_multi().into_iter().flatten() {
+                assert!(
+                    syllables.contains(&pinyin.plain()),
+                    "{c} 缺少读音 {}",
+                    pinyin.plain()
+                );
+            }
+        }
+        assert_eq!(pinyin_syllables('项'), vec!["xiang"]);
+    }
+
+    #[test]
+    fn test_kana_readings() {
+        assert_eq!(readings("さくら", 0), vec!["sa"]);
+        // 片假名与平假名读音相同
+        assert_eq!(readings("サクラ", 1), vec!["ku"]);
+        assert_eq!(readings("し", 0), vec!["shi", "si"]);
+    }
+
+    #[test]
+    fn test_kana_readings_with_context() {
+        // 拗音
+        assert_eq!(readings("きょう", 0), vec!["ki", "k"]);
+        assert_eq!(readings("きょう", 1), vec!["yo"]);
+        assert_eq!(readings("しゃ", 1), vec!["ya", "a"]);
+        // 促音与长音
+        assert_eq!(readings("がっこう", 1), vec!["k"]);
+        assert_eq!(readings("まっちゃ", 1), vec!["t", "c"]);
+        assert_eq!(readings("コーヒー", 1), vec!["o"]);
+        assert!(readings("ー", 0).is_empty());
+    }
+}
@@ -1,7 +1,11 @@
 //! 系统集成模块
 //! 
-//! 提供系统托盘、窗口管理、目录打开等系统级功能
+//! 提供系统托盘、窗口管理、目录打开、终端打开等系统级功能
 
+use crate::launchers::{expand_placeholders, resolve_executable, split_exec};
+use crate::search::{resolve_hit_path, FrecencyStore, SearchDirectoryInput};
+use std::path::Path;
+use std::process::Command;
 use std::sync::{
     atomic::{AtomicBool, Ordering},
     Arc,
@@ -43,6 +47,115 @@ pub fn open_directory(path: String) -> Result<(), String> {
     Ok(())
 }
 
+/// Linux 下未设置 `$TERMINAL` 时依次尝试的终端（x-terminal-emulator 为 Debian 系的系统默认终端）
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const LINUX_TERMINALS: &[&str] = &[
+    "x-terminal-emulator",
+    "gnome-terminal",
+    "konsole",
+    "xfce4-terminal",
+    "kitty",
+    "alacritty",
+    "wezterm",
+    "xterm",
+];
+
+/// 在工作区打开终端，并记录打开历史
+///
+/// `terminal` 为设置中的终端命令（如 `wezterm start --cwd {path}`，支持 `{path}`、`{name}` 占位符），
+/// 为空时使用系统默认终端。终端的工作目录始终为工作区。
+#[tauri::command]
+pub fn open_terminal(
+    frecency: tauri::State<'_, FrecencyStore>,
+    path: String,
+    directories: Vec<SearchDirectoryInput>,
+    terminal: Option<String>,
+) -> Result<(), String> {
+    let workspace = resolve_hit_path(&path, directories)?;
+    let mut command = terminal_command(terminal.as_deref(), Path::new(&workspace))?;
+    command.spawn().map_err(|e| format!("打开终端失败: {}", e))?;
+
+    frecency.record_open_best_effort(&workspace);
+    Ok(())
+}
+
+/// 选择终端：设置中的终端命令优先，其次是 `$TERMINAL`（仅 Linux），最后是系统默认终端
+///
+/// 终端命令按空白拆分为程序和参数，含空格的路径可用双引号包裹。
+fn terminal_command(terminal: Option<&str>, workspace: &Path) -> Result<Command, String> {
+    let path = workspace.to_string_lossy();
+    let name = workspace
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_else(|| path.clone());
+    let mut parts = terminal.map(split_exec).unwrap_or_default().into_iter();
+    if let Some(program) = parts.next() {
+        let executable =
+            resolve_executable(&program).ok_or_else(|| format!("找不到终端: {}", program))?;
+        let mut command = Command::new(executable);
+        command
+            .args(parts.map(|arg| expand_placeholders(&arg, &path, &name)))
+            .current_dir(workspace);
+        return Ok(command);
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        // 优先使用 Windows Terminal，否则用 start 在新窗口中打开 cmd
+        let mut command = match resolve_executable("wt") {
+            Some(wt) => {
+                let mut command = Command::new(wt);
+                command.arg("-d").arg(workspace);
+                command
+            }
+            None => {
+                let mut command = Command::new("cmd");
+                command.args(["/C", "start", "", "cmd"]);
+                command
+            }
+        };
+        command.current_dir(workspace);
+        Ok(command)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let mut command = Command::new("open");
+        command
+            .args(["-a", "Terminal"])
+            .arg(workspace)
+            .current_dir(workspace);
+        Ok(command)
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        let env_terminal = std::env::var("TERMINAL").ok();
+        let mut command = linux_terminal(env_terminal.as_deref())?;
+        command.current_dir(workspace);
+        Ok(command)
+    }
+}
+
+/// Linux 下的默认终端：优先使用 `$TERMINAL`（可以带参数，如 `kitty -1`），否则依次尝试内置列表
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn linux_terminal(env_terminal: Option<&str>) -> Result<Command, String> {
+    let env_parts = env_terminal.map(split_exec).unwrap_or_default();
+    let env_command = env_parts.split_first().and_then(|(program, args)| {
+        let mut command = Command::new(resolve_executable(program)?);
+        command.args(args);
+        Some(command)
+    });
+    match env_command {
+        Some(command) => Ok(command),
+        None => LINUX_TERMINALS
+            .iter()
+            .find_map(|t| resolve_executable(t))
+            .map(Command::new)
+            .ok_or_else(|| "找不到可用的终端，请在设置中指定终端命令".to_string()),
+    }
+}
+
 /// 初始化系统托盘
 pub fn setup_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     // 创建托盘菜单
@@ -239,3 +352,84 @@ pub fn setup_windows_and_shortcuts(
     Ok(main_focused)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn args(command: &Command) -> Vec<String> {
+        command
+            .get_args()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_terminal_command_override() {
+        let temp_dir = TempDir::new().unwrap();
+        let terminal = temp_dir.path().join("my-terminal");
+        fs::write(&terminal, "").unwrap();
+        let workspace = temp_dir.path().join("project");
+        fs::create_dir(&workspace).unwrap();
+
+        // 使用设置中的终端命令，并展开 {path} 与 {name}
+        let setting = format!(
+            "{} start --cwd {{path}} --title {{name}}",
+            terminal.display()
+        );
+        let command = terminal_command(Some(&setting), &workspace).unwrap();
+        assert_eq!(command.get_program(), terminal.as_os_str());
+        assert_eq!(
+            args(&command),
+            vec![
+                "start",
+                "--cwd",
+                &workspace.to_string_lossy(),
+                "--title",
+                "project"
+            ]
+        );
+        assert_eq!(command.get_current_dir(), Some(workspace.as_path()));
+
+        let result = terminal_command(Some("/nonexistent/term"), &workspace);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_terminal_command_quoted_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let terminal = temp_dir.path().join("My Terminal").join("term");
+        fs::create_dir(terminal.parent().unwrap()).unwrap();
+        fs::write(&terminal, "").unwrap();
+
+        // 含空格的程序路径用双引号包裹
+        let setting = format!("\"{}\" --working-directory {{path}}", terminal.display());
+        let command = terminal_command(Some(&setting), temp_dir.path()).unwrap();
+        assert_eq!(command.get_program(), terminal.as_os_str());
+        assert_eq!(
+            args(&command),
+            vec!["--working-directory", &temp_dir.path().to_string_lossy()]
+        );
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    #[test]
+    fn test_linux_terminal_env_terminal() {
+        let temp_dir = TempDir::new().unwrap();
+        let terminal = temp_dir.path().join("env-terminal");
+        fs::write(&terminal, "").unwrap();
+
+        // 设置了 $TERMINAL 时使用它
+        let env_terminal = terminal.to_string_lossy();
+        let command = linux_terminal(Some(&env_terminal)).unwrap();
+        assert_eq!(command.get_program(), terminal.as_os_str());
+        assert!(args(&command).is_empty());
+
+        // $TERMINAL 带参数时同样使用它，而不是回退到内置列表
+        let env_terminal = format!("{} -1", terminal.display());
+        let command = linux_terminal(Some(&env_terminal)).unwrap();
+        assert_eq!(command.get_program(), terminal.as_os_str());
+        assert_eq!(args(&command), vec!["-1"]);
+    }
+}
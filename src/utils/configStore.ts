@@ -6,6 +6,17 @@ export interface ShortcutsConfig {
   showSearchWindow: string;  // 显示/隐藏搜索窗口
 }
 
+// 模糊拼音规则（与后端 FuzzyPinyinRules 对应，每条规则双向生效）
+export interface FuzzyPinyinRules {
+  zhZ: boolean;    // zh ↔ z
+  chC: boolean;    // ch ↔ c
+  shS: boolean;    // sh ↔ s
+  nL: boolean;     // n ↔ l
+  anAng: boolean;  // an ↔ ang
+  enEng: boolean;  // en ↔ eng
+  inIng: boolean;  // in ↔ ing
+}
+
 // 配置接口
 export interface AppConfig {
   fontSize: number;
@@ -17,6 +28,8 @@ export interface AppConfig {
   notes: string[]; // 所有笔记的完整路径列表
   theme: 'light' | 'dark' | 'cyberpunk' | 'system'; // 主题模式
   shortcuts: ShortcutsConfig; // 快捷键配置
+  fuzzyPinyin: FuzzyPinyinRules; // 模糊拼音规则
+  terminal: string; // 终端命令（支持 {path} 占位符），为空时使用系统默认终端
 }
 
 // 默认快捷键配置
@@ -25,6 +38,17 @@ const DEFAULT_SHORTCUTS: ShortcutsConfig = {
   showSearchWindow: 'Ctrl+Space'
 };
 
+// 默认模糊拼音规则（全部关闭）
+const DEFAULT_FUZZY_PINYIN: FuzzyPinyinRules = {
+  zhZ: false,
+  chC: false,
+  shS: false,
+  nL: false,
+  anAng: false,
+  enEng: false,
+  inIng: false
+};
+
 // 默认配置
 const DEFAULT_CONFIG: AppConfig = {
   fontSize: 16,
@@ -35,7 +59,9 @@ const DEFAULT_CONFIG: AppConfig = {
   defaultNotesLocation: '', // 将在初始化时设置为 .ytools 目录
   notes: [],
   theme: 'system', // 默认跟随系统主题
-  shortcuts: DEFAULT_SHORTCUTS
+  shortcuts: DEFAULT_SHORTCUTS,
+  fuzzyPinyin: DEFAULT_FUZZY_PINYIN,
+  terminal: ''
 };
 
 // 创建 store 实例（使用 Store.load 加载）
@@ -137,7 +163,9 @@ export async function getConfig(): Promise<AppConfig> {
     defaultNotesLocation,
     notes,
     theme,
-    shortcuts
+    shortcuts,
+    fuzzyPinyin,
+    terminal
   ] = await Promise.all([
     getFontSize(),
     getFontFamily(),
@@ -147,7 +175,9 @@ export async function getConfig(): Promise<AppConfig> {
     getDefaultNotesLocation(),
     getNotes(),
     getTheme(),
-    getShortcuts()
+    getShortcuts(),
+    getFuzzyPinyin(),
+    getTerminal()
   ]);
 
   return {
@@ -159,7 +189,9 @@ export async function getConfig(): Promise<AppConfig> {
     defaultNotesLocation,
     notes,
     theme: theme as 'light' | 'dark' | 'cyberpunk' | 'system',
-    shortcuts
+    shortcuts,
+    fuzzyPinyin,
+    terminal
   };
 }
 
@@ -177,6 +209,8 @@ export async function resetConfig(): Promise<void> {
   await storeInstance.set('notes', DEFAULT_CONFIG.notes);
   await storeInstance.set('theme', DEFAULT_CONFIG.theme);
   await storeInstance.set('shortcuts', DEFAULT_CONFIG.shortcuts);
+  await storeInstance.set('fuzzyPinyin', DEFAULT_CONFIG.fuzzyPinyin);
+  await storeInstance.set('terminal', DEFAULT_CONFIG.terminal);
   await storeInstance.set('_migrated', true); // 保持迁移标记
   await storeInstance.save();
 }
@@ -339,6 +373,32 @@ export async function resetShortcuts(): Promise<void> {
   await setShortcuts(DEFAULT_SHORTCUTS);
 }
 
+// 获取模糊拼音规则
+export async function getFuzzyPinyin(): Promise<FuzzyPinyinRules> {
+  const storeInstance = await getStore();
+  const rules = await storeInstance.get<Partial<FuzzyPinyinRules>>('fuzzyPinyin');
+  return { ...DEFAULT_FUZZY_PINYIN, ...rules };
+}
+
+// 设置模糊拼音规则
+export async function setFuzzyPinyin(rules: FuzzyPinyinRules): Promise<void> {
+  const storeInstance = await getStore();
+  await storeInstance.set('fuzzyPinyin', rules);
+}
+
+// 获取终端命令
+export async function getTerminal(): Promise<string> {
+  const storeInstance = await getStore();
+  const terminal = await storeInstance.get<string>('terminal');
+  return terminal ?? DEFAULT_CONFIG.terminal;
+}
+
+// 设置终端命令
+export async function setTerminal(terminal: string): Promise<void> {
+  const storeInstance = await getStore();
+  await storeInstance.set('terminal', terminal);
+}
+
 // 导出默认快捷键配置（用于恢复默认）
-export { DEFAULT_SHORTCUTS };
+export { DEFAULT_SHORTCUTS, DEFAULT_FUZZY_PINYIN };
 
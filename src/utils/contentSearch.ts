@@ -0,0 +1,82 @@
+import { invoke } from '@tauri-apps/api/core'
+import { listen, type UnlistenFn } from '@tauri-apps/api/event'
+import { cancelSearch, nextRequestId, type RootStatus } from './searchStream'
+
+// 与后端 content 模块的事件名保持一致
+const HIT_EVENT = 'content-search-hit'
+const DONE_EVENT = 'content-search-done'
+
+// 内容搜索选项（与后端 ContentSearchOptions 对应）
+export interface ContentSearchOptions {
+  regex?: boolean
+  caseMode?: 'smart' | 'sensitive' | 'insensitive'
+  maxFileSize?: number
+  maxMatches?: number
+  maxDepth?: number
+  maxFiles?: number
+  rootTimeoutMs?: number
+}
+
+export interface ContentLine {
+  line: number
+  text: string
+  highlights: { start: number; end: number }[]
+}
+
+export interface ContentHit {
+  requestId: number
+  path: string
+  root: string
+  lines: ContentLine[]
+}
+
+export interface ContentSearchDone {
+  requestId: number
+  scannedFiles: number
+  matchedFiles: number
+  matchedLines: number
+  truncated: boolean
+  cancelled: boolean
+  // 已搜索的各目录状态
+  roots: RootStatus[]
+}
+
+/**
+ * 发起内容搜索，命中结果逐个文件回调
+ * 新的搜索会取消上一次搜索，过期请求的事件会被忽略
+ * @returns 用于取消本次搜索并停止监听的函数
+ */
+export async function searchContent(
+  query: string,
+  directories: string[],
+  options: ContentSearchOptions,
+  onHit: (hit: ContentHit) => void,
+  onDone: (done: ContentSearchDone) => void
+): Promise<() => void> {
+  const requestId = nextRequestId()
+  const unlisteners: UnlistenFn[] = []
+  const stop = () => unlisteners.splice(0).forEach((unlisten) => unlisten())
+
+  unlisteners.push(
+    await listen<ContentHit>(HIT_EVENT, (event) => {
+      if (event.payload.requestId === requestId) onHit(event.payload)
+    }),
+    await listen<ContentSearchDone>(DONE_EVENT, (event) => {
+      if (event.payload.requestId !== requestId) return
+      stop()
+      onDone(event.payload)
+    })
+  )
+
+  try {
+    await invoke('search_content', { requestId, query, directories, options })
+  } catch (error) {
+    stop()
+    throw error
+  }
+
+  return () => {
+    stop()
+    cancelSearch(requestId)
+  }
+}
@@ -0,0 +1,35 @@
+import { invoke } from '@tauri-apps/api/core'
+
+// 启动器定义（与后端 Launcher 对应）
+export interface Launcher {
+  id: string
+  name: string
+  executable: string
+  // 参数模板，支持 {path}、{name} 占位符
+  args: string[]
+  workingDir?: string | null
+  env?: Record<string, string>
+}
+
+export async function listLaunchers(): Promise<Launcher[]> {
+  return invoke<Launcher[]>('list_launchers')
+}
+
+// 探测本机已安装的编辑器与终端，返回的定义需调用 addLauncher 保存
+export async function detectLaunchers(): Promise<Launcher[]> {
+  return invoke<Launcher[]>('detect_launchers')
+}
+
+// 添加启动器，标识相同时替换原定义；可执行文件不存在时抛出错误
+export async function addLauncher(launcher: Launcher): Promise<Launcher> {
+  return invoke<Launcher>('add_launcher', { launcher })
+}
+
+export async function removeLauncher(id: string): Promise<void> {
+  await invoke('remove_launcher', { id })
+}
+
+// 用启动器打开搜索结果
+export async function invokeLauncher(id: string, path: string, directories: string[]): Promise<void> {
+  await invoke('invoke_launcher', { id, path, directories })
+}
@@ -0,0 +1,79 @@
+import { invoke } from '@tauri-apps/api/core'
+import { listen, type UnlistenFn } from '@tauri-apps/api/event'
+
+// 与后端 stream 模块的事件名保持一致
+const PARTIAL_EVENT = 'search-partial'
+const DONE_EVENT = 'search-done'
+
+// 所有后台搜索共用的请求 id，保证工作区搜索与内容搜索的 id 不会重复
+let lastRequestId = 0
+
+export function nextRequestId(): number {
+  lastRequestId += 1
+  return lastRequestId
+}
+
+// 取消后台搜索，不传 id 时取消所有搜索
+export async function cancelSearch(requestId?: number): Promise<void> {
+  await invoke('cancel_search', { requestId }).catch(() => {})
+}
+
+// 搜索目录状态（与后端 RootStatus 对应）
+export interface RootStatus {
+  root: string
+  state: 'ok' | 'missing' | 'permissionDenied' | 'timedOut'
+  entries: number
+}
+
+export interface SearchPartial<Hit> extends RootStatus {
+  requestId: number
+  hits: Hit[]
+}
+
+export interface SearchDone<Hit> {
+  requestId: number
+  hits: Hit[]
+  roots: RootStatus[]
+  cancelled: boolean
+}
+
+/**
+ * 发起流式工作区/文件搜索
+ * 每个搜索目录完成时回调 onPartial，全部完成（或被取消）时回调 onDone；过期请求的事件会被忽略
+ * @returns 用于取消本次搜索并停止监听的函数
+ */
+export async function streamSearch<Hit>(
+  mode: 'workspaces' | 'files',
+  query: string,
+  directories: string[],
+  options: Record<string, unknown>,
+  onPartial: (partial: SearchPartial<Hit>) => void,
+  onDone: (done: SearchDone<Hit>) => void
+): Promise<() => void> {
+  const requestId = nextRequestId()
+  const unlisteners: UnlistenFn[] = []
+  const stop = () => unlisteners.splice(0).forEach((unlisten) => unlisten())
+
+  unlisteners.push(
+    await listen<SearchPartial<Hit>>(PARTIAL_EVENT, (event) => {
+      if (event.payload.requestId === requestId) onPartial(event.payload)
+    }),
+    await listen<SearchDone<Hit>>(DONE_EVENT, (event) => {
+      if (event.payload.requestId !== requestId) return
+      stop()
+      onDone(event.payload)
+    })
+  )
+
+  try {
+    await invoke('start_search', { request: { requestId, mode, query, directories, options } })
+  } catch (error) {
+    stop()
+    throw error
+  }
+
+  return () => {
+    stop()
+    cancelSearch(requestId)
+  }
+}